@@ -0,0 +1,17 @@
+use pgp::composed::{SignedPublicKey, TheRing};
+
+/// Type name of PrivateKeyProvider, the allowed type of properties accepting any private key service
+pub(crate) const PRIVATE_KEY_PROVIDER: &str = concat!(module_path!(), "::PrivateKeyProvider");
+
+/// Type name of PublicKeyProvider, the allowed type of properties accepting any public key service
+pub(crate) const PUBLIC_KEY_PROVIDER: &str = concat!(module_path!(), "::PublicKeyProvider");
+
+/// Service interface for the private keys used by DecryptContentPGP
+pub(crate) trait PrivateKeyProvider {
+    fn get_the_ring(&self) -> TheRing<'_>;
+}
+
+/// Service interface for the public keys used by EncryptContentPGP
+pub(crate) trait PublicKeyProvider {
+    fn get_public_key(&self, target_id: &str) -> Option<&SignedPublicKey>;
+}
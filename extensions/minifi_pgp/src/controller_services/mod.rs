@@ -1,2 +1,3 @@
+pub(crate) mod key_providers;
 pub(crate) mod private_key_service;
 pub(crate) mod public_key_service;
@@ -1,8 +1,9 @@
 mod controller_service_definition;
 mod properties;
 
+use crate::controller_services::key_providers::PrivateKeyProvider;
 use minifi_native::macros::ComponentIdentifier;
-use minifi_native::{EnableControllerService, GetProperty, Logger, MinifiError};
+use minifi_native::{EnableControllerService, GetProperty, InterfaceRequest, Logger, MinifiError};
 use pgp::composed::{Deserializable, SignedSecretKey, TheRing};
 use pgp::types::Password;
 
@@ -50,6 +51,16 @@ impl EnableControllerService for PGPPrivateKeyService {
             passphrase,
         })
     }
+
+    fn provide_interfaces<'a>(&'a self, request: &mut InterfaceRequest<'a>) {
+        request.provide::<dyn PrivateKeyProvider>(self);
+    }
+}
+
+impl PrivateKeyProvider for PGPPrivateKeyService {
+    fn get_the_ring(&self) -> TheRing<'_> {
+        PGPPrivateKeyService::get_the_ring(self)
+    }
 }
 
 impl PGPPrivateKeyService {
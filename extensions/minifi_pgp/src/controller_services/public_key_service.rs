@@ -1,9 +1,10 @@
 mod controller_service_definition;
 mod properties;
 
+use crate::controller_services::key_providers::PublicKeyProvider;
 use crate::controller_services::public_key_service::properties::{KEYRING, KEYRING_FILE};
 use minifi_native::macros::ComponentIdentifier;
-use minifi_native::{EnableControllerService, GetProperty, Logger, MinifiError};
+use minifi_native::{EnableControllerService, GetProperty, InterfaceRequest, Logger, MinifiError};
 use pgp::composed::{Deserializable, SignedPublicKey};
 
 #[derive(Debug, ComponentIdentifier, PartialEq)]
@@ -39,6 +40,16 @@ impl EnableControllerService for PGPPublicKeyService {
         }
         Ok(Self { public_keys })
     }
+
+    fn provide_interfaces<'a>(&'a self, request: &mut InterfaceRequest<'a>) {
+        request.provide::<dyn PublicKeyProvider>(self);
+    }
+}
+
+impl PublicKeyProvider for PGPPublicKeyService {
+    fn get_public_key(&self, target_id: &str) -> Option<&SignedPublicKey> {
+        self.get(target_id)
+    }
 }
 
 impl PGPPublicKeyService {
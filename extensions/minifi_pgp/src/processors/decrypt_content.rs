@@ -2,10 +2,12 @@ mod output_attributes;
 pub(crate) mod properties;
 mod relationships;
 
-use crate::controller_services::private_key_service::PGPPrivateKeyService;
+use crate::controller_services::key_providers::PrivateKeyProvider;
 use crate::processors::decrypt_content::properties::{PRIVATE_KEY_SERVICE, SYMMETRIC_PASSWORD};
 use crate::processors::decrypt_content::relationships::{FAILURE, SUCCESS};
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
     FlowFileStreamTransform, GetControllerService, GetProperty, InputStream, Logger, MinifiError,
     OutputStream, Schedule, TransformStreamResult, warn,
@@ -22,7 +24,23 @@ enum DecryptionStrategy {
     Packaged,
 }
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Decrypt contents of OpenPGP messages. Using the Packaged Decryption Strategy preserves OpenPGP encoding to support subsequent signature verification.",
+    input = Required,
+    relationships = [SUCCESS, FAILURE],
+    properties = [
+        properties::DECRYPTION_STRATEGY,
+        properties::SYMMETRIC_PASSWORD,
+        properties::PRIVATE_KEY_SERVICE,
+    ],
+    output_attributes = [
+        output_attributes::LITERAL_DATA_FILENAME,
+        output_attributes::LITERAL_DATA_MODIFIED,
+    ],
+)]
 pub(crate) struct DecryptContentPGP {
     decompress_data: bool,
     symmetric_password: Option<pgp::types::Password>,
@@ -35,9 +53,10 @@ impl Schedule for DecryptContentPGP {
         L: Logger,
     {
         let decryption_strategy = context
-            .get_property(&properties::DECRYPTION_STRATEGY)?
-            .expect("required property")
-            .parse::<DecryptionStrategy>()?;
+            .get_enum_property::<DecryptionStrategy>(&properties::DECRYPTION_STRATEGY)?
+            .ok_or(MinifiError::MissingRequiredProperty(
+                properties::DECRYPTION_STRATEGY.name,
+            ))?;
 
         let symmetric_password = context
             .get_property(&SYMMETRIC_PASSWORD)?
@@ -68,7 +87,7 @@ impl DecryptContentPGP {
         L: Logger,
     {
         let private_key_service = ctx
-            .get_controller_service::<PGPPrivateKeyService>(&PRIVATE_KEY_SERVICE)
+            .get_controller_service_dyn::<dyn PrivateKeyProvider>(&PRIVATE_KEY_SERVICE)
             .unwrap_or(None);
         let mut ring = if let Some(pks) = private_key_service {
             pks.get_the_ring()
@@ -148,5 +167,3 @@ impl FlowFileStreamTransform for DecryptContentPGP {
 
 #[cfg(test)]
 mod tests;
-
-mod processor_definition;
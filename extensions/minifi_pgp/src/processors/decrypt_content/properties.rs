@@ -1,5 +1,4 @@
-use crate::controller_services::private_key_service::PGPPrivateKeyService;
-use minifi_native::ComponentIdentifier;
+use crate::controller_services::key_providers::PRIVATE_KEY_PROVIDER;
 use minifi_native::{Property, StandardPropertyValidator};
 use strum::VariantNames;
 
@@ -36,5 +35,5 @@ pub(crate) const PRIVATE_KEY_SERVICE: Property = Property {
     default_value: None,
     validator: StandardPropertyValidator::AlwaysValidValidator,
     allowed_values: &[],
-    allowed_type: PGPPrivateKeyService::CLASS_NAME,
+    allowed_type: PRIVATE_KEY_PROVIDER,
 };
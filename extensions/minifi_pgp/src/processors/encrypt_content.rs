@@ -1,6 +1,6 @@
 use minifi_native::{
     FlowFileStreamTransform, GetAttribute, GetControllerService, GetProperty, InputStream, Logger,
-    MinifiError, OutputStream, Schedule, TransformStreamResult, warn,
+    MinifiError, OutputStream, Schedule, TransformStreamResult, core_attributes, warn,
 };
 use pgp::composed::{ArmorOptions, MessageBuilder, SignedPublicKey};
 use pgp::types::StringToKey;
@@ -10,13 +10,15 @@ mod output_attributes;
 mod properties;
 mod relationships;
 
-use crate::controller_services::public_key_service::PGPPublicKeyService;
+use crate::controller_services::key_providers::PublicKeyProvider;
 use crate::processors::encrypt_content::output_attributes::FILE_ENCODING;
 use crate::processors::encrypt_content::properties::{
     PASSWORD, PUBLIC_KEY_SEARCH, PUBLIC_KEY_SERVICE,
 };
 use crate::processors::encrypt_content::relationships::{FAILURE, SUCCESS};
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use strum_macros::{Display, EnumString, IntoStaticStr, VariantNames};
 
 #[derive(Debug, Clone, Copy, PartialEq, Display, EnumString, VariantNames, IntoStaticStr)]
@@ -26,7 +28,21 @@ enum FileEncoding {
     Binary,
 }
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Encrypt contents using OpenPGP.",
+    input = Required,
+    relationships = [SUCCESS, FAILURE],
+    properties = [
+        properties::FILE_ENCODING,
+        PASSWORD,
+        PUBLIC_KEY_SEARCH,
+        PUBLIC_KEY_SERVICE,
+    ],
+    output_attributes = [FILE_ENCODING],
+)]
 pub(crate) struct EncryptContentPGP {
     file_encoding: FileEncoding,
 }
@@ -80,9 +96,10 @@ impl Schedule for EncryptContentPGP {
         Self: Sized,
     {
         let file_encoding = context
-            .get_property(&properties::FILE_ENCODING)?
-            .expect("required property")
-            .parse::<FileEncoding>()?;
+            .get_enum_property::<FileEncoding>(&properties::FILE_ENCODING)?
+            .ok_or(MinifiError::MissingRequiredProperty(
+                properties::FILE_ENCODING.name,
+            ))?;
 
         let has_password = context.get_property(&PASSWORD)?.is_some();
         let has_public_key = context.get_property(&PUBLIC_KEY_SERVICE)?.is_some()
@@ -107,12 +124,14 @@ impl FlowFileStreamTransform for EncryptContentPGP {
         output_stream: &mut dyn OutputStream,
         logger: &LoggerImpl,
     ) -> Result<TransformStreamResult, MinifiError> {
-        let file_name = context.get_attribute("filename")?.unwrap_or_default();
+        let file_name = context
+            .get_attribute(core_attributes::FILENAME)?
+            .unwrap_or_default();
         let public_key = if let (Some(pub_key_search), Some(public_key_service)) = (
             context.get_property(&PUBLIC_KEY_SEARCH)?,
-            context.get_controller_service::<PGPPublicKeyService>(&PUBLIC_KEY_SERVICE)?,
+            context.get_controller_service_dyn::<dyn PublicKeyProvider>(&PUBLIC_KEY_SERVICE)?,
         ) {
-            public_key_service.get(&pub_key_search)
+            public_key_service.get_public_key(&pub_key_search)
         } else {
             None
         };
@@ -146,5 +165,3 @@ impl FlowFileStreamTransform for EncryptContentPGP {
 
 #[cfg(test)]
 mod tests;
-
-mod processor_definition;
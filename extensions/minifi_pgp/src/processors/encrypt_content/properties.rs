@@ -1,6 +1,5 @@
-use crate::controller_services::public_key_service::PGPPublicKeyService;
+use crate::controller_services::key_providers::PUBLIC_KEY_PROVIDER;
 use crate::processors::encrypt_content::FileEncoding;
-use minifi_native::ComponentIdentifier;
 use minifi_native::{Property, StandardPropertyValidator};
 use strum::VariantNames;
 
@@ -49,5 +48,5 @@ pub(crate) const PUBLIC_KEY_SERVICE: Property = Property {
     default_value: None,
     validator: StandardPropertyValidator::AlwaysValidValidator,
     allowed_values: &[],
-    allowed_type: PGPPublicKeyService::CLASS_NAME,
+    allowed_type: PUBLIC_KEY_PROVIDER,
 };
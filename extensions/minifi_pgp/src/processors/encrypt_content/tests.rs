@@ -1,4 +1,5 @@
 use super::*;
+use crate::controller_services::public_key_service::PGPPublicKeyService;
 use crate::test_utils;
 use minifi_native::{
     ComponentIdentifier, EnableControllerService, IoState, MockControllerServiceContext,
@@ -21,7 +21,7 @@ Feature: API error handling and logging
 
     When the MiNiFi instance starts up
 
-    Then the Minifi logs contain the following message: "KamikazeProcessorRs] [error] Error during on_schedule: ScheduleError("it was designed to fail during schedule")" in less than 10 seconds
+    Then the Minifi logs contain the following message: "KamikazeProcessorRs] [error] Error in KamikazeProcessorRs during on_schedule: schedule failed: it was designed to fail during schedule" in less than 10 seconds
     And the Minifi logs contain the following message: "(KamikazeProcessorRs): Process Schedule Operation: Error while scheduling processor" in less than 10 seconds
 
   Scenario: Minifi handles errors from on_trigger
@@ -31,7 +31,7 @@ Feature: API error handling and logging
 
     When the MiNiFi instance starts up
 
-    Then the Minifi logs contain the following message: "KamikazeProcessorRs] [error] Error during on_trigger TriggerError("it was designed to fail in trigger")" in less than 10 seconds
+    Then the Minifi logs contain the following message: "KamikazeProcessorRs] [error] Error in KamikazeProcessorRs during on_trigger: trigger failed: it was designed to fail in trigger" in less than 10 seconds
     And the Minifi logs contain the following message: "Trigger and commit failed for processor KamikazeProcessorRs" in less than 10 seconds
 
   Scenario: Panic in extension's on_schedule crashes the agent aswell
@@ -1,8 +1,10 @@
 mod controller_services;
+mod playground_state;
 mod processors;
 
 use crate::controller_services::dummy_controller_service::DummyControllerService;
 use crate::controller_services::lorem_ipsum_controller_service::LoremIpsumControllerService;
+use crate::playground_state::PlaygroundState;
 use crate::processors::asciify_german::AsciifyGerman;
 use crate::processors::count_actual_logging::CountActualLogging;
 use crate::processors::duplicate_text::DuplicateStreamText;
@@ -11,14 +13,18 @@ use crate::processors::get_file::GetFileRs;
 use crate::processors::kamikaze_processor::KamikazeProcessorRs;
 use crate::processors::log_attribute::LogAttributeRs;
 use crate::processors::lorem_ipsum_cs_user::LoremIpsumCSUser;
+use crate::processors::merge_text::MergeTextRs;
 use crate::processors::put_file::PutFileRs;
+use crate::processors::split_text::SplitTextRs;
 
 use minifi_native::{
-    ComplexProcessorType, Concurrent, Exclusive, FlowFileSourceProcessorType,
+    ComplexProcessorType, Concurrent, Exclusive, FlowFileAggregatorProcessorType,
+    FlowFileSourceProcessorType, FlowFileSplitTransformProcessorType,
     FlowFileStreamTransformProcessorType, FlowFileTransformProcessorType,
 };
 
 minifi_native::declare_minifi_extension!(
+state: PlaygroundState,
 processors: [
     (GenerateFlowFileRs, ComplexProcessorType, Concurrent),
     (LogAttributeRs, ComplexProcessorType, Concurrent),
@@ -29,6 +35,8 @@ processors: [
     (AsciifyGerman, FlowFileStreamTransformProcessorType, Concurrent),
     (CountActualLogging, ComplexProcessorType, Exclusive),
     (DuplicateStreamText, FlowFileStreamTransformProcessorType, Exclusive),
+    (SplitTextRs, FlowFileSplitTransformProcessorType, Concurrent),
+    (MergeTextRs, FlowFileAggregatorProcessorType, Exclusive),
 ],
 controllers: [
     LoremIpsumControllerService,
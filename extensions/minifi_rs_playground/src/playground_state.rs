@@ -0,0 +1,25 @@
+use minifi_native::{ExtensionConfig, ExtensionState, MinifiError};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Shared by every processor of the extension for as long as the extension is loaded
+#[derive(Debug, Default)]
+pub(crate) struct PlaygroundState {
+    generated_flow_files: AtomicU64,
+}
+
+impl PlaygroundState {
+    pub(crate) fn add_generated_flow_files(&self, count: u64) {
+        self.generated_flow_files
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn generated_flow_files(&self) -> u64 {
+        self.generated_flow_files.load(Ordering::Relaxed)
+    }
+}
+
+impl ExtensionState for PlaygroundState {
+    fn init(_config: &ExtensionConfig) -> Result<Self, MinifiError> {
+        Ok(Self::default())
+    }
+}
@@ -1,14 +1,24 @@
 use crate::processors::asciify_german::relationships::FAILURE;
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
     FlowFileStreamTransform, GetProperty, InputStream, Logger, MinifiError, OutputStream, Schedule,
-    TransformStreamResult,
+    TransformBatch, TransformStreamResult,
 };
 use std::collections::HashMap;
+use std::time::Duration;
 
 mod relationships;
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "This processor switches German characters with their ascii counterparts. (to test stream API)",
+    input = Required,
+    relationships = [relationships::SUCCESS, relationships::FAILURE],
+)]
 pub(crate) struct AsciifyGerman {}
 
 impl Schedule for AsciifyGerman {
@@ -21,6 +31,9 @@ impl Schedule for AsciifyGerman {
 }
 
 impl FlowFileStreamTransform for AsciifyGerman {
+    const TRANSFORM_BATCH: TransformBatch =
+        TransformBatch::up_to(100, &FAILURE).with_time_budget(Duration::from_millis(50));
+
     fn transform<Ctx: GetProperty, LoggerImpl: Logger>(
         &self,
         _context: &Ctx,
@@ -62,6 +75,5 @@ impl FlowFileStreamTransform for AsciifyGerman {
     }
 }
 
-mod processor_definition;
 #[cfg(test)]
 mod tests;
@@ -1,11 +1,15 @@
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
-    GetProperty, Logger, MinifiError, MutTrigger, OnTriggerResult, OutputAttribute, ProcessContext,
-    ProcessSession, ProcessorDefinition, ProcessorInputRequirement, Property, Relationship,
+    GetProperty, Logger, MinifiError, MutTrigger, OnTriggerResult, ProcessContext, ProcessSession,
     Schedule, debug, info, trace,
 };
 
-#[derive(Debug, DefaultMetrics, NoAdvancedProcessorFeatures, ComponentIdentifier)]
+#[derive(
+    Debug, DefaultMetrics, NoAdvancedProcessorFeatures, ComponentIdentifier, ProcessorDefinition,
+)]
+#[processor(description = "For testing lazy logging", input = Forbidden)]
 pub(crate) struct CountActualLogging {
     log_count: usize,
 }
@@ -45,13 +49,3 @@ impl MutTrigger for CountActualLogging {
         Ok(OnTriggerResult::Ok)
     }
 }
-
-impl ProcessorDefinition for CountActualLogging {
-    const DESCRIPTION: &'static str = "For testing lazy logging";
-    const INPUT_REQUIREMENT: ProcessorInputRequirement = ProcessorInputRequirement::Forbidden;
-    const SUPPORTS_DYNAMIC_PROPERTIES: bool = false;
-    const SUPPORTS_DYNAMIC_RELATIONSHIPS: bool = false;
-    const OUTPUT_ATTRIBUTES: &'static [OutputAttribute] = &[];
-    const RELATIONSHIPS: &'static [Relationship] = &[];
-    const PROPERTIES: &'static [Property] = &[];
-}
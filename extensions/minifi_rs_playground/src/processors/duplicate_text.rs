@@ -1,12 +1,16 @@
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
     GetAttribute, GetControllerService, GetProperty, InputStream, Logger, MinifiError,
-    MutFlowFileStreamTransform, OutputAttribute, OutputStream, ProcessorDefinition,
-    ProcessorInputRequirement, Property, Relationship, Schedule, TransformStreamResult,
+    MutFlowFileStreamTransform, OutputStream, Relationship, Schedule, TransformStreamResult,
 };
 use std::collections::HashMap;
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(description = "Duplicate text", input = Required, relationships = [SUCCESS])]
 pub(crate) struct DuplicateStreamText {}
 
 pub(crate) const SUCCESS: Relationship = Relationship {
@@ -42,13 +46,3 @@ impl MutFlowFileStreamTransform for DuplicateStreamText {
         Ok(TransformStreamResult::new(&SUCCESS, HashMap::new()))
     }
 }
-
-impl ProcessorDefinition for DuplicateStreamText {
-    const DESCRIPTION: &'static str = "Duplicate text";
-    const INPUT_REQUIREMENT: ProcessorInputRequirement = ProcessorInputRequirement::Required;
-    const SUPPORTS_DYNAMIC_PROPERTIES: bool = false;
-    const SUPPORTS_DYNAMIC_RELATIONSHIPS: bool = false;
-    const OUTPUT_ATTRIBUTES: &'static [OutputAttribute] = &[];
-    const RELATIONSHIPS: &'static [Relationship] = &[SUCCESS];
-    const PROPERTIES: &'static [Property] = &[];
-}
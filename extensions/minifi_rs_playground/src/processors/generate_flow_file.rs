@@ -1,15 +1,21 @@
-use minifi_native::macros::{ComponentIdentifier, NoAdvancedProcessorFeatures};
+use crate::playground_state::PlaygroundState;
+use minifi_native::macros::{
+    ComponentIdentifier, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
-    CalculateMetrics, GetProperty, Logger, MinifiError, OnTriggerResult, ProcessContext,
-    ProcessSession, Schedule, Trigger,
+    CalculateMetrics, GetConfig, GetExtensionState, GetProperty, Logger, MinifiError,
+    OnTriggerResult, ProcessContext, ProcessSession, Properties, Schedule, Trigger,
 };
 use rand::RngExt;
 use rand::distr::Alphanumeric;
 use std::cmp::PartialEq;
+use std::sync::Arc;
 
 mod properties;
 mod relationships;
 
+use properties::{DataFormat, GenerateFlowFileProperties};
+
 #[derive(Debug, PartialEq)]
 enum Mode {
     UniqueBytes,
@@ -20,35 +26,35 @@ enum Mode {
     Empty,
 }
 
-#[derive(Debug, ComponentIdentifier, NoAdvancedProcessorFeatures)]
+#[derive(Debug, ComponentIdentifier, NoAdvancedProcessorFeatures, ProcessorDefinition)]
+#[processor(
+    description = "This processor creates FlowFiles with random data or custom content. GenerateFlowFile is useful for load testing, configuration, and simulation.",
+    input = Forbidden,
+    relationships = [relationships::SUCCESS],
+    properties = GenerateFlowFileProperties::PROPERTIES,
+)]
 pub(crate) struct GenerateFlowFileRs {
     mode: Mode,
     batch_size: u64,
     file_size: u64,
     data_generated_during_on_schedule: Vec<u8>,
+    playground_state: Option<Arc<PlaygroundState>>,
 }
 
 impl Schedule for GenerateFlowFileRs {
-    fn schedule<P: GetProperty, L: Logger>(context: &P, _logger: &L) -> Result<Self, MinifiError>
+    fn schedule<P: GetProperty + GetConfig + GetExtensionState, L: Logger>(
+        context: &P,
+        _logger: &L,
+    ) -> Result<Self, MinifiError>
     where
         Self: Sized,
     {
-        let is_unique = context
-            .get_bool_property(&properties::UNIQUE_FLOW_FILES)?
-            .expect("Required property");
-        let is_text = context
-            .get_property(&properties::DATA_FORMAT)?
-            .expect("Required property")
-            .as_str()
-            == "Text";
-        let has_custom_text = context.get_property(&properties::CUSTOM_TEXT)?.is_some();
-
-        let file_size = context
-            .get_size_property(&properties::FILE_SIZE)?
-            .expect("Required property");
-        let batch_size = context
-            .get_u64_property(&properties::BATCH_SIZE)?
-            .expect("Required property");
+        let properties = GenerateFlowFileProperties::from_context(context)?;
+        let is_unique = properties.unique_flow_files;
+        let is_text = properties.data_format == DataFormat::Text;
+        let has_custom_text = properties.custom_text.is_some();
+        let file_size = properties.file_size.as_u64();
+        let batch_size = properties.batch_size;
 
         let mode = Self::get_mode(is_unique, is_text, has_custom_text, file_size);
         let data_generated_during_on_schedule =
@@ -65,6 +71,7 @@ impl Schedule for GenerateFlowFileRs {
             batch_size,
             file_size,
             data_generated_during_on_schedule,
+            playground_state: context.get_extension_state::<PlaygroundState>(),
         })
     }
 }
@@ -137,7 +144,8 @@ impl Trigger for GenerateFlowFileRs {
         let custom_text_for_batch: Option<String>;
 
         if self.mode == Mode::CustomText
-            && let Some(custom_text) = context.get_property(&properties::CUSTOM_TEXT, None)?
+            && let Some(custom_text) =
+                context.get_property(&GenerateFlowFileProperties::CUSTOM_TEXT, None)?
         {
             custom_text_for_batch = Some(custom_text);
             non_unique_data_buffer = custom_text_for_batch.as_ref().unwrap().as_bytes();
@@ -158,13 +166,26 @@ impl Trigger for GenerateFlowFileRs {
             }
             session.transfer(ff, relationships::SUCCESS.name)?;
         }
+        if let Some(playground_state) = &self.playground_state {
+            playground_state.add_generated_flow_files(self.batch_size);
+        }
         Ok(OnTriggerResult::Ok)
     }
 }
 
-impl CalculateMetrics for GenerateFlowFileRs {}
-
-pub(crate) mod processor_definition;
+impl CalculateMetrics for GenerateFlowFileRs {
+    fn calculate_metrics(&self) -> Vec<(String, f64)> {
+        self.playground_state
+            .as_ref()
+            .map(|playground_state| {
+                vec![(
+                    "ExtensionGeneratedFlowFiles".to_string(),
+                    playground_state.generated_flow_files() as f64,
+                )]
+            })
+            .unwrap_or_default()
+    }
+}
 
 #[cfg(test)]
 mod tests;
@@ -1,7 +1,4 @@
 use super::*;
-use crate::processors::generate_flow_file::properties::{
-    BATCH_SIZE, CUSTOM_TEXT, DATA_FORMAT, UNIQUE_FLOW_FILES,
-};
 use minifi_native::{MockLogger, MockProcessContext, MockProcessSession};
 
 #[test]
@@ -13,15 +10,20 @@ fn schedule_succeeds_with_default_values() {
 fn generate_flow_file_empty_test() {
     let logger = MockLogger::new();
     let mut context = MockProcessContext::new();
-    context
-        .properties
-        .insert(properties::FILE_SIZE.name.to_string(), "0".to_string());
-    context
-        .properties
-        .insert(UNIQUE_FLOW_FILES.name.to_string(), "false".to_string());
-    context
-        .properties
-        .insert(DATA_FORMAT.name.to_string(), "Text".to_string());
+    context.properties.insert(
+        GenerateFlowFileProperties::FILE_SIZE.name.to_string(),
+        "0".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::UNIQUE_FLOW_FILES
+            .name
+            .to_string(),
+        "false".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::DATA_FORMAT.name.to_string(),
+        "Text".to_string(),
+    );
 
     let processor = GenerateFlowFileRs::schedule(&context, &logger).unwrap();
     let mut session = MockProcessSession::new();
@@ -39,18 +41,24 @@ fn generate_flow_file_empty_test() {
 #[test]
 fn generate_custom_text() {
     let mut context = MockProcessContext::new();
-    context
-        .properties
-        .insert(properties::FILE_SIZE.name.to_string(), "0".to_string());
-    context
-        .properties
-        .insert(UNIQUE_FLOW_FILES.name.to_string(), "false".to_string());
-    context
-        .properties
-        .insert(DATA_FORMAT.name.to_string(), "Text".to_string());
-    context
-        .properties
-        .insert(CUSTOM_TEXT.name.to_string(), "foo bar baz".to_string());
+    context.properties.insert(
+        GenerateFlowFileProperties::FILE_SIZE.name.to_string(),
+        "0".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::UNIQUE_FLOW_FILES
+            .name
+            .to_string(),
+        "false".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::DATA_FORMAT.name.to_string(),
+        "Text".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::CUSTOM_TEXT.name.to_string(),
+        "foo bar baz".to_string(),
+    );
 
     let logger = MockLogger::new();
     let processor = GenerateFlowFileRs::schedule(&context, &logger).unwrap();
@@ -70,18 +78,24 @@ fn generate_custom_text() {
 #[test]
 fn random_bytes_unique() {
     let mut context = MockProcessContext::new();
-    context
-        .properties
-        .insert(properties::FILE_SIZE.name.to_string(), "40 B".to_string());
-    context
-        .properties
-        .insert(UNIQUE_FLOW_FILES.name.to_string(), "true".to_string());
-    context
-        .properties
-        .insert(DATA_FORMAT.name.to_string(), "Bytes".to_string());
-    context
-        .properties
-        .insert(BATCH_SIZE.name.to_string(), "2".to_string());
+    context.properties.insert(
+        GenerateFlowFileProperties::FILE_SIZE.name.to_string(),
+        "40 B".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::UNIQUE_FLOW_FILES
+            .name
+            .to_string(),
+        "true".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::DATA_FORMAT.name.to_string(),
+        "Binary".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::BATCH_SIZE.name.to_string(),
+        "2".to_string(),
+    );
 
     let logger = MockLogger::new();
     let processor = GenerateFlowFileRs::schedule(&context, &logger).unwrap();
@@ -105,18 +119,24 @@ fn random_bytes_unique() {
 #[test]
 fn random_bytes_non_unique() {
     let mut context = MockProcessContext::new();
-    context
-        .properties
-        .insert(properties::FILE_SIZE.name.to_string(), "40 B".to_string());
-    context
-        .properties
-        .insert(UNIQUE_FLOW_FILES.name.to_string(), "false".to_string());
-    context
-        .properties
-        .insert(DATA_FORMAT.name.to_string(), "Bytes".to_string());
-    context
-        .properties
-        .insert(BATCH_SIZE.name.to_string(), "2".to_string());
+    context.properties.insert(
+        GenerateFlowFileProperties::FILE_SIZE.name.to_string(),
+        "40 B".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::UNIQUE_FLOW_FILES
+            .name
+            .to_string(),
+        "false".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::DATA_FORMAT.name.to_string(),
+        "Binary".to_string(),
+    );
+    context.properties.insert(
+        GenerateFlowFileProperties::BATCH_SIZE.name.to_string(),
+        "2".to_string(),
+    );
 
     let logger = MockLogger::new();
     let processor = GenerateFlowFileRs::schedule(&context, &logger).unwrap();
@@ -136,3 +156,41 @@ fn random_bytes_non_unique() {
         *result_flow_files[1].flow_file.content.borrow()
     );
 }
+
+#[test]
+fn schedule_fails_with_invalid_data_format() {
+    let mut context = MockProcessContext::new();
+    context.properties.insert(
+        GenerateFlowFileProperties::DATA_FORMAT.name.to_string(),
+        "Bytes".to_string(),
+    );
+
+    assert!(GenerateFlowFileRs::schedule(&context, &MockLogger::new()).is_err());
+}
+
+#[test]
+fn generated_flow_files_are_counted_in_the_extension_state() {
+    let logger = MockLogger::new();
+    let mut context = MockProcessContext::new();
+    let playground_state = Arc::new(PlaygroundState::default());
+    context.extension_state = Some(playground_state.clone());
+    context.properties.insert(
+        GenerateFlowFileProperties::BATCH_SIZE.name.to_string(),
+        "3".to_string(),
+    );
+
+    let processor = GenerateFlowFileRs::schedule(&context, &logger).unwrap();
+    let mut session = MockProcessSession::new();
+    processor
+        .trigger(&mut context, &mut session, &logger)
+        .unwrap();
+    processor
+        .trigger(&mut context, &mut session, &logger)
+        .unwrap();
+
+    assert_eq!(playground_state.generated_flow_files(), 6);
+    assert_eq!(
+        processor.calculate_metrics(),
+        vec![("ExtensionGeneratedFlowFiles".to_string(), 6.0)]
+    );
+}
@@ -5,10 +5,12 @@ use crate::processors::get_file::properties::{
     BATCH_SIZE, DIRECTORY, IGNORE_HIDDEN_FILES, KEEP_SOURCE_FILE, MAX_AGE, MAX_SIZE, MIN_AGE,
     MIN_SIZE, RECURSE,
 };
-use minifi_native::macros::{ComponentIdentifier, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, Metrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
-    CalculateMetrics, GetProperty, IoState, Logger, MinifiError, OnTriggerResult, ProcessContext,
-    ProcessSession, Schedule, Trigger, debug, info, trace, warn,
+    CalculateMetrics, Counter, GetProperty, IoState, Logger, Metrics, MinifiError, OnTriggerResult,
+    ProcessContext, ProcessSession, Schedule, Trigger, debug, info, trace, warn,
 };
 use std::collections::VecDeque;
 use std::error;
@@ -21,10 +23,12 @@ use walkdir::{DirEntry, WalkDir};
 mod properties;
 mod relationships;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Metrics)]
 struct GetFileMetrics {
-    accepted_files: u32,
-    input_bytes: u64,
+    #[metric(name = "accepted_files")]
+    accepted_files: Counter,
+    #[metric(name = "input_bytes")]
+    input_bytes: Counter,
 }
 
 #[derive(Debug)]
@@ -42,7 +46,25 @@ impl DirectoryListing {
     }
 }
 
-#[derive(Debug, ComponentIdentifier, NoAdvancedProcessorFeatures)]
+#[derive(Debug, ComponentIdentifier, NoAdvancedProcessorFeatures, ProcessorDefinition)]
+#[processor(
+    description = "Creates FlowFiles from files in a directory. MiNiFi will ignore files for which it doesn't have read permissions.",
+    input = Forbidden,
+    relationships = [relationships::SUCCESS],
+    properties = [
+        DIRECTORY,
+        properties::POLLING_INTERVAL,
+        RECURSE,
+        KEEP_SOURCE_FILE,
+        MIN_AGE,
+        MAX_AGE,
+        MIN_SIZE,
+        MAX_SIZE,
+        IGNORE_HIDDEN_FILES,
+        BATCH_SIZE,
+    ],
+    output_attributes = [ABSOLUTE_PATH_OUTPUT_ATTRIBUTE, FILENAME_OUTPUT_ATTRIBUTE],
+)]
 pub(crate) struct GetFileRs {
     recursive: bool,
     keep_source_file: bool,
@@ -55,7 +77,7 @@ pub(crate) struct GetFileRs {
     min_age: Option<Duration>,
     max_age: Option<Duration>,
     ignore_hidden_files: bool,
-    metrics: Mutex<GetFileMetrics>,
+    metrics: GetFileMetrics,
 }
 
 impl GetFileRs {
@@ -99,19 +121,14 @@ impl GetFileRs {
             walker = walker.max_depth(1);
         }
 
-        let mut files_added = 0u32;
-        let mut bytes_added = 0u64;
         for entry in walker.into_iter().filter_map(Result::ok) {
             if self.entry_matches_criteria(&entry).unwrap_or(false) {
                 let file_size = entry.metadata().map(|m| m.len()).unwrap_or(0);
                 directory_listings.paths.push_back(entry.into_path());
-                files_added += 1;
-                bytes_added += file_size;
+                self.metrics.accepted_files.increment();
+                self.metrics.input_bytes.add(file_size);
             }
         }
-        let mut metrics = self.metrics.lock().unwrap();
-        metrics.accepted_files += files_added;
-        metrics.input_bytes += bytes_added;
         directory_listings.last_polling_time = Some(Instant::now());
     }
 
@@ -252,10 +269,7 @@ impl Schedule for GetFileRs {
             min_age,
             max_age,
             ignore_hidden_files,
-            metrics: Mutex::new(GetFileMetrics {
-                accepted_files: 0,
-                input_bytes: 0,
-            }),
+            metrics: GetFileMetrics::default(),
         })
     }
 }
@@ -306,16 +320,10 @@ impl Trigger for GetFileRs {
 
 impl CalculateMetrics for GetFileRs {
     fn calculate_metrics(&self) -> Vec<(String, f64)> {
-        let metrics = self.metrics.lock().unwrap();
-        vec![
-            ("accepted_files".to_string(), metrics.accepted_files as f64),
-            ("input_bytes".to_string(), metrics.input_bytes as f64),
-        ]
+        self.metrics.collect()
     }
 }
 
-pub(crate) mod processor_definition;
-
 mod output_attributes;
 #[cfg(test)]
 mod tests;
@@ -1,13 +1,13 @@
-use minifi_native::OutputAttribute;
+use minifi_native::{OutputAttribute, core_attributes};
 
 pub(crate) const FILENAME_OUTPUT_ATTRIBUTE: OutputAttribute = OutputAttribute {
-    name: "filename",
+    name: core_attributes::FILENAME,
     relationships: &["success"],
     description: "The filename is set to the name of the file on disk",
 };
 
 pub(crate) const ABSOLUTE_PATH_OUTPUT_ATTRIBUTE: OutputAttribute = OutputAttribute {
-    name: "absolute.path",
+    name: core_attributes::ABSOLUTE_PATH,
     relationships: &["success"],
     description: "The full/absolute path from where a file was picked up. The current 'path' attribute is still populated, but may be a relative path",
 };
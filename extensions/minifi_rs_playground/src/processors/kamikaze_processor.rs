@@ -2,11 +2,15 @@ mod properties;
 mod relationships;
 
 use crate::controller_services::lorem_ipsum_controller_service::LoremIpsumControllerService;
-use crate::processors::kamikaze_processor::properties::NOT_REGISTERED_PROPERTY;
-use minifi_native::macros::{ComponentIdentifier, NoAdvancedProcessorFeatures};
+use crate::processors::kamikaze_processor::properties::{
+    KamikazeProperties, NOT_REGISTERED_PROPERTY,
+};
+use minifi_native::macros::{
+    ComponentIdentifier, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
-    CalculateMetrics, GetProperty, Logger, MinifiError, OnTriggerResult, ProcessContext,
-    ProcessSession, Schedule, Trigger,
+    CalculateMetrics, EnumPropertyValue, GetProperty, Logger, MinifiError, OnTriggerResult,
+    ProcessContext, ProcessSession, Properties, Schedule, Trigger,
 };
 use strum_macros::{Display, EnumString, IntoStaticStr, VariantNames};
 
@@ -20,7 +24,15 @@ enum KamikazeBehaviour {
     Panic,
 }
 
-#[derive(Debug, ComponentIdentifier, NoAdvancedProcessorFeatures)]
+impl EnumPropertyValue for KamikazeBehaviour {}
+
+#[derive(Debug, ComponentIdentifier, NoAdvancedProcessorFeatures, ProcessorDefinition)]
+#[processor(
+    description = "This processor can fail or panic in on_trigger and on_schedule calls based on configuration. Only for testing purposes.",
+    input = Allowed,
+    relationships = [relationships::SUCCESS],
+    properties = KamikazeProperties::PROPERTIES,
+)]
 pub(crate) struct KamikazeProcessorRs {
     on_trigger_behaviour: KamikazeBehaviour,
 }
@@ -30,15 +42,10 @@ impl Schedule for KamikazeProcessorRs {
     where
         Self: Sized,
     {
-        let on_trigger_behaviour = context
-            .get_property(&properties::ON_TRIGGER_BEHAVIOUR)?
-            .expect("required property")
-            .parse::<KamikazeBehaviour>()?;
-
-        let on_schedule_behaviour = context
-            .get_property(&properties::ON_SCHEDULE_BEHAVIOUR)?
-            .expect("required property")
-            .parse::<KamikazeBehaviour>()?;
+        let KamikazeProperties {
+            on_schedule_behaviour,
+            on_trigger_behaviour,
+        } = KamikazeProperties::from_context(context)?;
 
         match on_schedule_behaviour {
             KamikazeBehaviour::ReturnErr => Err(MinifiError::schedule_err(
@@ -99,7 +106,5 @@ impl Trigger for KamikazeProcessorRs {
 
 impl CalculateMetrics for KamikazeProcessorRs {}
 
-pub(crate) mod processor_definition;
-
 #[cfg(test)]
 mod tests;
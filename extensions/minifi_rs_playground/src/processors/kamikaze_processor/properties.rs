@@ -1,30 +1,23 @@
 use crate::processors::kamikaze_processor::KamikazeBehaviour;
+use minifi_native::macros::Properties;
 use minifi_native::{Property, StandardPropertyValidator};
-use strum::VariantNames;
 
-pub(crate) const ON_SCHEDULE_BEHAVIOUR: Property = Property {
-    name: "On Schedule Behaviour",
-    description: "What to do during the on_schedule method",
-    is_required: true,
-    is_sensitive: false,
-    supports_expr_lang: false,
-    default_value: Some(KamikazeBehaviour::ReturnOk.into_str()),
-    validator: StandardPropertyValidator::AlwaysValidValidator,
-    allowed_values: &KamikazeBehaviour::VARIANTS,
-    allowed_type: "",
-};
+#[derive(Debug, Properties)]
+pub(crate) struct KamikazeProperties {
+    #[property(
+        name = "On Schedule Behaviour",
+        description = "What to do during the on_schedule method",
+        default = KamikazeBehaviour::ReturnOk.into_str()
+    )]
+    pub(crate) on_schedule_behaviour: KamikazeBehaviour,
 
-pub(crate) const ON_TRIGGER_BEHAVIOUR: Property = Property {
-    name: "On Trigger Behaviour",
-    description: "What to do during the on_trigger method",
-    is_required: true,
-    is_sensitive: false,
-    supports_expr_lang: false,
-    default_value: Some(KamikazeBehaviour::ReturnOk.into_str()),
-    validator: StandardPropertyValidator::AlwaysValidValidator,
-    allowed_values: &KamikazeBehaviour::VARIANTS,
-    allowed_type: "",
-};
+    #[property(
+        name = "On Trigger Behaviour",
+        description = "What to do during the on_trigger method",
+        default = KamikazeBehaviour::ReturnOk.into_str()
+    )]
+    pub(crate) on_trigger_behaviour: KamikazeBehaviour,
+}
 
 pub(crate) const NOT_REGISTERED_PROPERTY: Property = Property {
     name: "Kamikaze Processor Property",
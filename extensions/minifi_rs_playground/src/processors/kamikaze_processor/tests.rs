@@ -1,7 +1,4 @@
 use super::*;
-use crate::processors::kamikaze_processor::properties::{
-    ON_SCHEDULE_BEHAVIOUR, ON_TRIGGER_BEHAVIOUR,
-};
 use minifi_native::MinifiError::{ScheduleError, TriggerError};
 use minifi_native::{MockLogger, MockProcessContext, MockProcessSession};
 use std::panic::AssertUnwindSafe;
@@ -17,7 +14,7 @@ fn on_schedule_ok() {
 fn on_schedule_err() {
     let mut context = MockProcessContext::new();
     context.properties.insert(
-        ON_SCHEDULE_BEHAVIOUR.name.to_string(),
+        KamikazeProperties::ON_SCHEDULE_BEHAVIOUR.name.to_string(),
         "ReturnErr".to_string(),
     );
     let processor = KamikazeProcessorRs::schedule(&context, &MockLogger::new());
@@ -27,9 +24,10 @@ fn on_schedule_err() {
 #[test]
 fn on_schedule_panic() {
     let mut context = MockProcessContext::new();
-    context
-        .properties
-        .insert(ON_SCHEDULE_BEHAVIOUR.name.to_string(), "Panic".to_string());
+    context.properties.insert(
+        KamikazeProperties::ON_SCHEDULE_BEHAVIOUR.name.to_string(),
+        "Panic".to_string(),
+    );
 
     let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
         KamikazeProcessorRs::schedule(&context, &MockLogger::new())
@@ -55,7 +53,7 @@ fn on_trigger_ok() {
 fn on_trigger_err() {
     let mut context = MockProcessContext::new();
     context.properties.insert(
-        ON_TRIGGER_BEHAVIOUR.name.to_string(),
+        KamikazeProperties::ON_TRIGGER_BEHAVIOUR.name.to_string(),
         "ReturnErr".to_string(),
     );
     let processor = KamikazeProcessorRs::schedule(&context, &MockLogger::new()).unwrap();
@@ -70,9 +68,10 @@ fn on_trigger_err() {
 #[test]
 fn on_trigger_panic() {
     let mut context = MockProcessContext::new();
-    context
-        .properties
-        .insert(ON_TRIGGER_BEHAVIOUR.name.to_string(), "Panic".to_string());
+    context.properties.insert(
+        KamikazeProperties::ON_TRIGGER_BEHAVIOUR.name.to_string(),
+        "Panic".to_string(),
+    );
     let processor = KamikazeProcessorRs::schedule(&context, &MockLogger::new()).unwrap();
 
     let mut session = MockProcessSession::new();
@@ -1,5 +1,7 @@
 use crate::processors::log_attribute::properties::{FLOW_FILES_TO_LOG, LOG_LEVEL, LOG_PAYLOAD};
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
     GetProperty, LogLevel, Logger, MinifiError, OnTriggerResult, ProcessContext, ProcessSession,
     Property, Schedule, Trigger, debug, log, trace,
@@ -8,7 +10,23 @@ use minifi_native::{
 mod properties;
 mod relationships;
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Logs attributes of flow files in the MiNiFi application log.",
+    input = Required,
+    relationships = [relationships::SUCCESS],
+    properties = [
+        LOG_LEVEL,
+        properties::ATTRIBUTES_TO_LOG,
+        properties::ATTRIBUTES_TO_IGNORE,
+        LOG_PAYLOAD,
+        properties::LOG_PREFIX,
+        FLOW_FILES_TO_LOG,
+        properties::HEX_ENCODE_PAYLOAD,
+    ],
+)]
 pub(crate) struct LogAttributeRs {
     log_level: LogLevel,
     attributes_to_log: Option<Vec<String>>,
@@ -106,9 +124,8 @@ impl Schedule for LogAttributeRs {
         Self: Sized,
     {
         let log_level = context
-            .get_property(&LOG_LEVEL)?
-            .expect("required property")
-            .parse::<LogLevel>()?;
+            .get_enum_property::<LogLevel>(&LOG_LEVEL)?
+            .ok_or(MinifiError::MissingRequiredProperty(LOG_LEVEL.name))?;
 
         let log_payload = context
             .get_bool_property(&LOG_PAYLOAD)?
@@ -154,7 +171,5 @@ impl Schedule for LogAttributeRs {
     }
 }
 
-pub(crate) mod processor_definition;
-
 #[cfg(test)]
 mod tests;
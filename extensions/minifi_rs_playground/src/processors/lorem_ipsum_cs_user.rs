@@ -3,7 +3,9 @@ mod properties;
 use crate::controller_services::lorem_ipsum_controller_service::LoremIpsumControllerService;
 use crate::processors::lorem_ipsum_cs_user::properties::CONTROLLER_SERVICE;
 use crate::processors::lorem_ipsum_cs_user::relationships::SUCCESS;
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
     Content, FlowFileSource, GeneratedFlowFile, GetControllerService, GetProperty, Logger,
     MinifiError, Schedule, trace,
@@ -18,7 +20,15 @@ enum WriteMethod {
     Stream,
 }
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Processor to test Controller Service API",
+    input = Forbidden,
+    relationships = [SUCCESS],
+    properties = [CONTROLLER_SERVICE, properties::WRITE_METHOD],
+)]
 pub(crate) struct LoremIpsumCSUser {
     write_method: WriteMethod,
 }
@@ -29,9 +39,10 @@ impl Schedule for LoremIpsumCSUser {
         Self: Sized,
     {
         let write_method = context
-            .get_property(&properties::WRITE_METHOD)?
-            .expect("required property")
-            .parse::<WriteMethod>()?;
+            .get_enum_property::<WriteMethod>(&properties::WRITE_METHOD)?
+            .ok_or(MinifiError::MissingRequiredProperty(
+                properties::WRITE_METHOD.name,
+            ))?;
         Ok(Self { write_method })
     }
 }
@@ -68,8 +79,6 @@ impl FlowFileSource for LoremIpsumCSUser {
     }
 }
 
-pub(crate) mod processor_definition;
-
 mod relationships;
 #[cfg(test)]
 mod tests;
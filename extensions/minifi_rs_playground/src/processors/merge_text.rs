@@ -0,0 +1,111 @@
+use crate::processors::merge_text::output_attributes::MERGE_COUNT;
+use crate::processors::merge_text::properties::MergeTextProperties;
+use crate::processors::merge_text::relationships::{BINNED, MERGED, ORIGINAL};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
+use minifi_native::{
+    BinEntry, BinLimits, FlowFileAggregator, GetAttribute, GetControllerService, GetProperty,
+    Logger, MergedFlowFile, MinifiError, Properties, Relationship, Schedule,
+};
+use std::collections::HashMap;
+
+mod output_attributes;
+mod properties;
+mod relationships;
+
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Merges the text content of FlowFiles with the same correlation attribute value. (to test the aggregator API) The binned relationship has to be connected back to this processor.",
+    input = Required,
+    relationships = [MERGED, ORIGINAL, BINNED],
+    properties = MergeTextProperties::PROPERTIES,
+    output_attributes = [MERGE_COUNT],
+)]
+pub(crate) struct MergeTextRs {
+    correlation_attribute_name: Option<String>,
+    demarcator: Vec<u8>,
+    limits: BinLimits,
+}
+
+impl Schedule for MergeTextRs {
+    fn schedule<P: GetProperty, L: Logger>(context: &P, _logger: &L) -> Result<Self, MinifiError>
+    where
+        Self: Sized,
+    {
+        let properties = MergeTextProperties::from_context(context)?;
+        if properties.max_entries == 0 || properties.min_entries > properties.max_entries {
+            return Err(MinifiError::schedule_err(
+                "Maximum Number of Entries must be positive and at least the Minimum Number of Entries",
+            ));
+        }
+
+        let mut limits = BinLimits::new(
+            properties.min_entries as usize,
+            properties.max_entries as usize,
+        );
+        if let Some(max_bin_size) = properties.max_bin_size {
+            limits = limits.with_max_bin_size(max_bin_size.as_u64());
+        }
+        if let Some(max_bin_age) = properties.max_bin_age {
+            limits = limits.with_max_bin_age(max_bin_age);
+        }
+
+        Ok(Self {
+            correlation_attribute_name: properties.correlation_attribute_name,
+            demarcator: properties.demarcator.unwrap_or_default().into_bytes(),
+            limits,
+        })
+    }
+}
+
+impl FlowFileAggregator for MergeTextRs {
+    const ORIGINAL_RELATIONSHIP: Relationship = ORIGINAL;
+    const BINNED_RELATIONSHIP: Relationship = BINNED;
+
+    fn bin_limits(&self) -> &BinLimits {
+        &self.limits
+    }
+
+    fn correlation_key<Ctx: GetProperty + GetControllerService + GetAttribute>(
+        &self,
+        context: &Ctx,
+    ) -> Result<String, MinifiError> {
+        match &self.correlation_attribute_name {
+            Some(attribute_name) => Ok(context.get_attribute(attribute_name)?.unwrap_or_default()),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn merge<LoggerImpl: Logger>(
+        &mut self,
+        _correlation_key: &str,
+        entries: &[BinEntry],
+        _logger: &LoggerImpl,
+    ) -> Result<MergedFlowFile, MinifiError> {
+        let mut content = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            if i > 0 {
+                content.extend_from_slice(&self.demarcator);
+            }
+            content.extend_from_slice(entry.content());
+        }
+
+        let mut attributes =
+            HashMap::from([(MERGE_COUNT.name.to_string(), entries.len().to_string())]);
+        if let Some(attribute_name) = &self.correlation_attribute_name
+            && let Some(value) = entries
+                .first()
+                .and_then(|e| e.get_attribute(attribute_name))
+        {
+            attributes.insert(attribute_name.clone(), value.to_string());
+        }
+
+        Ok(MergedFlowFile::new(&MERGED, content, attributes))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,7 @@
+use minifi_native::OutputAttribute;
+
+pub(crate) const MERGE_COUNT: OutputAttribute = OutputAttribute {
+    name: "merge.count",
+    relationships: &["merged"],
+    description: "The number of FlowFiles that were merged into this bundle",
+};
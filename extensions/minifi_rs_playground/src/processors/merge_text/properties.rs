@@ -0,0 +1,44 @@
+use minifi_native::DataSize;
+use minifi_native::macros::Properties;
+use std::time::Duration;
+
+#[derive(Debug, Properties)]
+pub(crate) struct MergeTextProperties {
+    #[property(
+        name = "Correlation Attribute Name",
+        description = "If specified, only FlowFiles that have the same value for this attribute will be merged together"
+    )]
+    pub(crate) correlation_attribute_name: Option<String>,
+
+    #[property(
+        name = "Minimum Number of Entries",
+        description = "The minimum number of files to include in a bundle, bins with fewer entries are not merged when they reach the Max Bin Age",
+        default = "1"
+    )]
+    pub(crate) min_entries: u64,
+
+    #[property(
+        name = "Maximum Number of Entries",
+        description = "The maximum number of files to include in a bundle",
+        default = "1000"
+    )]
+    pub(crate) max_entries: u64,
+
+    #[property(
+        name = "Maximum Group Size",
+        description = "The maximum size for the bundle. If not specified, there is no maximum."
+    )]
+    pub(crate) max_bin_size: Option<DataSize>,
+
+    #[property(
+        name = "Max Bin Age",
+        description = "The maximum age of a bin that will trigger the bin to be merged. If not specified, bins are only merged when they are full."
+    )]
+    pub(crate) max_bin_age: Option<Duration>,
+
+    #[property(
+        name = "Demarcator",
+        description = "Text that is inserted between the contents of the merged FlowFiles"
+    )]
+    pub(crate) demarcator: Option<String>,
+}
@@ -0,0 +1,16 @@
+use minifi_native::Relationship;
+
+pub(crate) const MERGED: Relationship = Relationship {
+    name: "merged",
+    description: "The FlowFile containing the merged content",
+};
+
+pub(crate) const ORIGINAL: Relationship = Relationship {
+    name: "original",
+    description: "The FlowFiles that were used to create the bundle",
+};
+
+pub(crate) const BINNED: Relationship = Relationship {
+    name: "binned",
+    description: "The FlowFiles waiting for their bin to be ready, connect it back to this processor",
+};
@@ -0,0 +1,89 @@
+use super::*;
+use minifi_native::{MockLogger, MockProcessContext};
+
+fn entry(content: &str, group: &str) -> BinEntry {
+    BinEntry::new(
+        content.as_bytes().to_vec(),
+        HashMap::from([("group".to_string(), group.to_string())]),
+    )
+}
+
+#[test]
+fn schedule_succeeds_with_default_values() {
+    let merge_text = MergeTextRs::schedule(&MockProcessContext::new(), &MockLogger::new())
+        .expect("Should succeed");
+    assert_eq!(merge_text.bin_limits().max_entries(), 1000);
+    assert_eq!(merge_text.bin_limits().max_bin_age(), None);
+}
+
+#[test]
+fn schedule_fails_with_min_entries_above_max() {
+    let mut context = MockProcessContext::new();
+    context
+        .properties
+        .insert(MergeTextProperties::MIN_ENTRIES.name, "10");
+    context
+        .properties
+        .insert(MergeTextProperties::MAX_ENTRIES.name, "5");
+    assert!(MergeTextRs::schedule(&context, &MockLogger::new()).is_err());
+}
+
+#[test]
+fn schedule_with_max_bin_age() {
+    let mut context = MockProcessContext::new();
+    context
+        .properties
+        .insert(MergeTextProperties::MAX_BIN_AGE.name, "10s");
+    let merge_text = MergeTextRs::schedule(&context, &MockLogger::new()).expect("Should succeed");
+    assert_eq!(
+        merge_text.bin_limits().max_bin_age(),
+        Some(std::time::Duration::from_secs(10))
+    );
+}
+
+#[test]
+fn correlation_key_from_attribute() {
+    let mut context = MockProcessContext::new();
+    context.properties.insert(
+        MergeTextProperties::CORRELATION_ATTRIBUTE_NAME.name,
+        "group",
+    );
+    let merge_text = MergeTextRs::schedule(&context, &MockLogger::new()).expect("Should succeed");
+
+    context
+        .attributes
+        .insert("group".to_string(), "a".to_string());
+    assert_eq!(merge_text.correlation_key(&context).unwrap(), "a");
+}
+
+#[test]
+fn merges_with_demarcator() {
+    let mut context = MockProcessContext::new();
+    context.properties.insert(
+        MergeTextProperties::CORRELATION_ATTRIBUTE_NAME.name,
+        "group",
+    );
+    context
+        .properties
+        .insert(MergeTextProperties::DEMARCATOR.name, ", ");
+    let logger = MockLogger::new();
+    let mut merge_text = MergeTextRs::schedule(&context, &logger).expect("Should succeed");
+
+    let merged = merge_text
+        .merge(
+            "a",
+            &[entry("one", "a"), entry("two", "a"), entry("three", "a")],
+            &logger,
+        )
+        .expect("Should succeed");
+    assert_eq!(merged.target_relationship_name(), MERGED.name);
+    assert_eq!(merged.content(), b"one, two, three");
+    assert_eq!(
+        merged.attributes_to_add().get(MERGE_COUNT.name),
+        Some(&"3".to_string())
+    );
+    assert_eq!(
+        merged.attributes_to_add().get("group"),
+        Some(&"a".to_string())
+    );
+}
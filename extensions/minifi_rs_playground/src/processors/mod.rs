@@ -6,4 +6,6 @@ pub(crate) mod get_file;
 pub(crate) mod kamikaze_processor;
 pub(crate) mod log_attribute;
 pub(crate) mod lorem_ipsum_cs_user;
+pub(crate) mod merge_text;
 pub(crate) mod put_file;
+pub(crate) mod split_text;
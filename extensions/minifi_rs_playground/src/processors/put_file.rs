@@ -1,8 +1,10 @@
 use crate::processors::put_file::relationships::{FAILURE, SUCCESS};
-use minifi_native::macros::{ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
 use minifi_native::{
     FlowFileTransform, GetAttribute, GetControllerService, GetProperty, InputStream, Logger,
-    MinifiError, Schedule, TransformedFlowFile, trace, warn,
+    MinifiError, Schedule, TransformedFlowFile, core_attributes, trace, warn,
 };
 use std::path::{Path, PathBuf};
 use strum_macros::{Display, EnumString, IntoStaticStr, VariantNames};
@@ -13,6 +15,28 @@ mod relationships;
 #[cfg(unix)]
 mod unix_only_properties;
 
+#[cfg(windows)]
+const fn get_properties() -> &'static [minifi_native::Property] {
+    &[
+        properties::DIRECTORY,
+        properties::CONFLICT_RESOLUTION,
+        properties::CREATE_DIRS,
+        properties::MAX_FILE_COUNT,
+    ]
+}
+
+#[cfg(unix)]
+const fn get_properties() -> &'static [minifi_native::Property] {
+    &[
+        properties::DIRECTORY,
+        properties::CONFLICT_RESOLUTION,
+        properties::CREATE_DIRS,
+        properties::MAX_FILE_COUNT,
+        unix_only_properties::PERMISSIONS,
+        unix_only_properties::DIRECTORY_PERMISSIONS,
+    ]
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Display, EnumString, VariantNames, IntoStaticStr)]
 #[strum(serialize_all = "camelCase", const_into_str)]
 enum ConflictResolutionStrategy {
@@ -60,7 +84,15 @@ impl PutFileUnixPermissions {
     }
 }
 
-#[derive(Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures)]
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Writes the contents of a FlowFile to the local file system.",
+    input = Required,
+    relationships = [SUCCESS, FAILURE],
+    properties = get_properties(),
+)]
 pub(crate) struct PutFileRs {
     conflict_resolution_strategy: ConflictResolutionStrategy,
     try_make_dirs: bool,
@@ -88,7 +120,7 @@ impl PutFileRs {
             .expect("required property");
 
         let file_name = context
-            .get_attribute("filename")?
+            .get_attribute(core_attributes::FILENAME)?
             .unwrap_or("foo.txt".to_string()); // fallback to UUID
         Ok(PathBuf::from(directory + "/" + file_name.as_str()))
     }
@@ -163,9 +195,10 @@ impl PutFileRs {
 impl Schedule for PutFileRs {
     fn schedule<P: GetProperty, L: Logger>(context: &P, _logger: &L) -> Result<Self, MinifiError> {
         let conflict_resolution_strategy = context
-            .get_property(&properties::CONFLICT_RESOLUTION)?
-            .expect("required property")
-            .parse::<ConflictResolutionStrategy>()?;
+            .get_enum_property::<ConflictResolutionStrategy>(&properties::CONFLICT_RESOLUTION)?
+            .ok_or(MinifiError::MissingRequiredProperty(
+                properties::CONFLICT_RESOLUTION.name,
+            ))?;
 
         let try_make_dirs = context
             .get_bool_property(&properties::CREATE_DIRS)?
@@ -228,7 +261,5 @@ impl FlowFileTransform for PutFileRs {
     }
 }
 
-pub(crate) mod processor_definition;
-
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,90 @@
+use crate::processors::split_text::output_attributes::{FRAGMENT_COUNT, FRAGMENT_INDEX};
+use crate::processors::split_text::properties::SplitTextProperties;
+use crate::processors::split_text::relationships::{ORIGINAL, SPLITS};
+use minifi_native::macros::{
+    ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+};
+use minifi_native::{
+    FlowFileSplitTransform, GetAttribute, GetControllerService, GetProperty, InputStream, Logger,
+    MinifiError, Properties, Schedule, SplitFlowFile, SplitTransformResult,
+};
+use std::collections::HashMap;
+
+mod output_attributes;
+mod properties;
+mod relationships;
+
+#[derive(
+    Debug, ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+)]
+#[processor(
+    description = "Splits a text file into multiple smaller text files on line boundaries. (to test the split transform API)",
+    input = Required,
+    relationships = [SPLITS, ORIGINAL],
+    properties = SplitTextProperties::PROPERTIES,
+    output_attributes = [FRAGMENT_INDEX, FRAGMENT_COUNT],
+)]
+pub(crate) struct SplitTextRs {
+    line_split_count: usize,
+}
+
+impl Schedule for SplitTextRs {
+    fn schedule<P: GetProperty, L: Logger>(context: &P, _logger: &L) -> Result<Self, MinifiError>
+    where
+        Self: Sized,
+    {
+        let properties = SplitTextProperties::from_context(context)?;
+        if properties.line_split_count == 0 {
+            return Err(MinifiError::schedule_err(
+                "Line Split Count must be greater than zero",
+            ));
+        }
+        Ok(Self {
+            line_split_count: properties.line_split_count as usize,
+        })
+    }
+}
+
+impl FlowFileSplitTransform for SplitTextRs {
+    fn transform<Ctx: GetProperty + GetControllerService + GetAttribute, LoggerImpl: Logger>(
+        &self,
+        _context: &Ctx,
+        input_stream: &mut dyn InputStream,
+        _logger: &LoggerImpl,
+    ) -> Result<SplitTransformResult, MinifiError> {
+        let mut fragments: Vec<Vec<u8>> = Vec::new();
+        let mut current_fragment = Vec::new();
+        let mut lines_in_current_fragment = 0;
+        while input_stream.read_until(b'\n', &mut current_fragment)? > 0 {
+            lines_in_current_fragment += 1;
+            if lines_in_current_fragment == self.line_split_count {
+                fragments.push(std::mem::take(&mut current_fragment));
+                lines_in_current_fragment = 0;
+            }
+        }
+        if !current_fragment.is_empty() {
+            fragments.push(current_fragment);
+        }
+
+        let fragment_count = fragments.len().to_string();
+        let splits = fragments
+            .into_iter()
+            .enumerate()
+            .map(|(index, fragment)| {
+                SplitFlowFile::new(
+                    &SPLITS,
+                    fragment,
+                    HashMap::from([
+                        (FRAGMENT_INDEX.name.to_string(), (index + 1).to_string()),
+                        (FRAGMENT_COUNT.name.to_string(), fragment_count.clone()),
+                    ]),
+                )
+            })
+            .collect();
+
+        Ok(SplitTransformResult::with_original(splits, &ORIGINAL))
+    }
+}
+
+#[cfg(test)]
+mod tests;
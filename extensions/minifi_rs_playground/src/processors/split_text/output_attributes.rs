@@ -0,0 +1,13 @@
+use minifi_native::OutputAttribute;
+
+pub(crate) const FRAGMENT_INDEX: OutputAttribute = OutputAttribute {
+    name: "fragment.index",
+    relationships: &["splits"],
+    description: "A one-up number that indicates the ordering of the split FlowFiles that were created from a single parent FlowFile",
+};
+
+pub(crate) const FRAGMENT_COUNT: OutputAttribute = OutputAttribute {
+    name: "fragment.count",
+    relationships: &["splits"],
+    description: "The number of split FlowFiles generated from the parent FlowFile",
+};
@@ -0,0 +1,11 @@
+use minifi_native::macros::Properties;
+
+#[derive(Debug, Properties)]
+pub(crate) struct SplitTextProperties {
+    #[property(
+        name = "Line Split Count",
+        description = "The number of lines that will be added to each split file",
+        default = "1"
+    )]
+    pub(crate) line_split_count: u64,
+}
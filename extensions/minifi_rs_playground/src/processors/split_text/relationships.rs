@@ -0,0 +1,11 @@
+use minifi_native::Relationship;
+
+pub(crate) const SPLITS: Relationship = Relationship {
+    name: "splits",
+    description: "The split files will be routed to this relationship",
+};
+
+pub(crate) const ORIGINAL: Relationship = Relationship {
+    name: "original",
+    description: "The original input file will be routed to this destination when it has been successfully split",
+};
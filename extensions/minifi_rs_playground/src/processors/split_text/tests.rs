@@ -0,0 +1,64 @@
+use super::*;
+use minifi_native::{MockLogger, MockProcessContext};
+use std::io::BufReader;
+
+#[test]
+fn schedule_succeeds_with_default_values() {
+    assert!(SplitTextRs::schedule(&MockProcessContext::new(), &MockLogger::new()).is_ok());
+}
+
+#[test]
+fn schedule_fails_with_zero_line_split_count() {
+    let mut context = MockProcessContext::new();
+    context
+        .properties
+        .insert(SplitTextProperties::LINE_SPLIT_COUNT.name, "0");
+    assert!(SplitTextRs::schedule(&context, &MockLogger::new()).is_err());
+}
+
+#[test]
+fn splits_every_two_lines() {
+    let mut context = MockProcessContext::new();
+    context
+        .properties
+        .insert(SplitTextProperties::LINE_SPLIT_COUNT.name, "2");
+    let logger = MockLogger::new();
+    let split_text = SplitTextRs::schedule(&context, &logger).expect("Should succeed");
+
+    let mut input_stream = BufReader::new("one\ntwo\nthree\nfour\nfive".as_bytes());
+    let result = split_text
+        .transform(&context, &mut input_stream, &logger)
+        .expect("Should succeed");
+
+    assert_eq!(result.original_relationship_name(), Some(ORIGINAL.name));
+    let splits = result.splits();
+    assert_eq!(splits.len(), 3);
+    assert_eq!(splits[0].content(), b"one\ntwo\n");
+    assert_eq!(splits[1].content(), b"three\nfour\n");
+    assert_eq!(splits[2].content(), b"five");
+    for (i, split) in splits.iter().enumerate() {
+        assert_eq!(split.target_relationship_name(), SPLITS.name);
+        assert_eq!(
+            split.attributes_to_add().get(FRAGMENT_INDEX.name),
+            Some(&(i + 1).to_string())
+        );
+        assert_eq!(
+            split.attributes_to_add().get(FRAGMENT_COUNT.name),
+            Some(&"3".to_string())
+        );
+    }
+}
+
+#[test]
+fn empty_input_has_no_splits() {
+    let context = MockProcessContext::new();
+    let logger = MockLogger::new();
+    let split_text = SplitTextRs::schedule(&context, &logger).expect("Should succeed");
+
+    let mut input_stream = BufReader::new("".as_bytes());
+    let result = split_text
+        .transform(&context, &mut input_stream, &logger)
+        .expect("Should succeed");
+    assert!(result.splits().is_empty());
+    assert_eq!(result.original_relationship_name(), Some(ORIGINAL.name));
+}
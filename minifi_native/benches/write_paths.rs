@@ -0,0 +1,124 @@
+//! Compares the write paths of ProcessSession on the mock session and on the CffiProcessSession,
+//! the latter against a fake agent that collects the written content in memory
+use criterion::{
+    BenchmarkGroup, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main,
+    measurement::WallTime,
+};
+use minifi_native::c_ffi::{CffiFlowFile, CffiProcessSession};
+use minifi_native::{IoState, MockFlowFile, MockProcessSession, ProcessSession};
+use std::hint::black_box;
+
+// the size of LoremIpsumCSUser's payload
+const PAYLOAD_SIZE: usize = 200 * 1024;
+const CHUNK_SIZES: [usize; 3] = [8 * 1024, 64 * 1024, 0];
+
+// Stands in for the agent's write functions, the output stream passed to the callback is a Vec<u8>
+mod fake_agent {
+    use minifi_native::sys::{
+        MinifiFlowFile, MinifiOutputStream, MinifiProcessSession, MinifiStatus,
+        MinifiStatus_MINIFI_STATUS_SUCCESS,
+    };
+    use std::ffi::{c_char, c_void};
+    use std::hint::black_box;
+
+    #[unsafe(no_mangle)]
+    unsafe extern "C" fn MinifiProcessSessionWrite(
+        _session: *mut MinifiProcessSession,
+        _flow_file: *mut MinifiFlowFile,
+        cb: Option<unsafe extern "C" fn(*mut c_void, *mut MinifiOutputStream) -> i64>,
+        user_ctx: *mut c_void,
+    ) -> MinifiStatus {
+        let mut content: Vec<u8> = Vec::new();
+        let cb = cb.expect("the session always passes a write callback");
+        unsafe { cb(user_ctx, &mut content as *mut _ as *mut MinifiOutputStream) };
+        black_box(content);
+        MinifiStatus_MINIFI_STATUS_SUCCESS
+    }
+
+    #[unsafe(no_mangle)]
+    unsafe extern "C" fn MinifiOutputStreamWrite(
+        stream: *mut MinifiOutputStream,
+        data: *const c_char,
+        size: usize,
+    ) -> i64 {
+        unsafe {
+            let content = &mut *(stream as *mut Vec<u8>);
+            content.extend_from_slice(std::slice::from_raw_parts(data as *const u8, size));
+        }
+        size as i64
+    }
+}
+
+fn bench_session<PS: ProcessSession>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    session: &PS,
+    flow_file: &PS::FlowFile,
+    payload: &[u8],
+) {
+    let (first_half, second_half) = payload.split_at(payload.len() / 2);
+
+    group.bench_function("write", |b| {
+        b.iter(|| session.write(flow_file, black_box(payload)).unwrap())
+    });
+    group.bench_function("write_lazy", |b| {
+        b.iter(|| {
+            session
+                .write_lazy(flow_file, Box::new(black_box(payload)))
+                .unwrap()
+        })
+    });
+    group.bench_function("write_stream", |b| {
+        b.iter(|| {
+            session
+                .write_stream(flow_file, |output_stream| {
+                    output_stream.write_all(black_box(payload))?;
+                    Ok(((), IoState::Ok))
+                })
+                .unwrap()
+        })
+    });
+    for chunk_size in CHUNK_SIZES {
+        group.bench_with_input(
+            BenchmarkId::new("write_vectored", chunk_size),
+            &chunk_size,
+            |b, &chunk_size| {
+                b.iter(|| {
+                    session
+                        .write_vectored(
+                            flow_file,
+                            black_box(&[first_half, second_half]),
+                            chunk_size,
+                        )
+                        .unwrap()
+                })
+            },
+        );
+    }
+}
+
+fn write_paths(c: &mut Criterion) {
+    let payload = vec![b'x'; PAYLOAD_SIZE];
+
+    let mut group = c.benchmark_group("mock_write_paths");
+    group.throughput(Throughput::Bytes(PAYLOAD_SIZE as u64));
+    bench_session(
+        &mut group,
+        &MockProcessSession::new(),
+        &MockFlowFile::new(),
+        &payload,
+    );
+    group.finish();
+
+    let mut group = c.benchmark_group("cffi_write_paths");
+    group.throughput(Throughput::Bytes(PAYLOAD_SIZE as u64));
+    bench_session(
+        &mut group,
+        &CffiProcessSession::new(std::ptr::null_mut()),
+        &CffiFlowFile::new(std::ptr::null_mut(), std::ptr::null_mut()),
+        &payload,
+    );
+    group.finish();
+}
+
+criterion_group!(benches, write_paths);
+criterion_main!(benches);
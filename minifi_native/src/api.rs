@@ -2,18 +2,25 @@ pub(crate) mod attribute;
 pub(crate) mod component_definition_traits;
 pub(crate) mod controller_service;
 pub(crate) mod errors;
+pub(crate) mod extension_config;
+pub(crate) mod extension_state;
 mod flow_file;
+pub(crate) mod input_stream;
+pub(crate) mod instance_metadata;
 pub(crate) mod logger;
+pub(crate) mod metrics;
 mod process_context;
 pub(crate) mod process_session;
 pub(crate) mod processor;
+pub(crate) mod processor_metrics;
 pub(crate) mod processor_wrappers;
 pub(crate) mod property;
 pub(crate) mod raw_controller_service;
 pub(crate) mod raw_processor;
 mod relationship;
+pub(crate) mod service_interface;
 
-pub use flow_file::FlowFile;
+pub use flow_file::{FlowFile, core_attributes};
 pub use logger::{LogLevel, Logger};
 pub use process_context::ProcessContext;
 pub use process_session::{InputStream, OutputStream, ProcessSession};
@@ -22,4 +29,4 @@ pub use raw_processor::{OnTriggerResult, ProcessorInputRequirement, RawProcessor
 
 pub use property::StandardPropertyValidator;
 
-pub use relationship::Relationship;
+pub use relationship::{DynamicRelationship, Relationship};
@@ -1,4 +1,4 @@
-use crate::{OutputAttribute, ProcessorInputRequirement, Property, Relationship};
+use crate::{DynamicProperty, OutputAttribute, ProcessorInputRequirement, Property, Relationship};
 
 pub trait ComponentIdentifier {
     const CLASS_NAME: &'static str;
@@ -14,9 +14,85 @@ pub trait ProcessorDefinition {
     const OUTPUT_ATTRIBUTES: &'static [OutputAttribute];
     const RELATIONSHIPS: &'static [Relationship];
     const PROPERTIES: &'static [Property];
+    const DYNAMIC_PROPERTIES: &'static [DynamicProperty];
 }
 
 pub trait ControllerServiceDefinition {
     const DESCRIPTION: &'static str;
     const PROPERTIES: &'static [Property];
 }
+
+const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < lhs.len() {
+        if lhs[i] != rhs[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+pub(crate) const fn relationship_is_listed(name: &str, relationships: &[Relationship]) -> bool {
+    let mut i = 0;
+    while i < relationships.len() {
+        if str_eq(name, relationships[i].name) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Checks that every relationship an OutputAttribute refers to is one of the relationships,
+/// #[derive(ProcessorDefinition)] evaluates this at compile time, for generic processors too.
+///
+/// ```compile_fail,E0080
+/// use minifi_native::macros::ProcessorDefinition;
+/// use minifi_native::{OutputAttribute, Relationship};
+/// use std::marker::PhantomData;
+///
+/// const SUCCESS: Relationship = Relationship {
+///     name: "success",
+///     description: "",
+/// };
+///
+/// const TAG: OutputAttribute = OutputAttribute {
+///     name: "tag",
+///     relationships: &["failure"],
+///     description: "",
+/// };
+///
+/// #[derive(ProcessorDefinition)]
+/// #[processor(description = "Tags flow files", relationships = [SUCCESS], output_attributes = [TAG])]
+/// struct Tagger<T>(PhantomData<T>);
+/// ```
+pub const fn output_attributes_match_relationships(
+    output_attributes: &[OutputAttribute],
+    relationships: &[Relationship],
+) -> bool {
+    let mut i = 0;
+    while i < output_attributes.len() {
+        let mut j = 0;
+        while j < output_attributes[i].relationships.len() {
+            let mut found = false;
+            let mut k = 0;
+            while k < relationships.len() {
+                if str_eq(output_attributes[i].relationships[j], relationships[k].name) {
+                    found = true;
+                }
+                k += 1;
+            }
+            if !found {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
@@ -1,10 +1,25 @@
 use crate::api::RawControllerService;
-use crate::{ComponentIdentifier, GetProperty, LogLevel, Logger, MinifiError};
+use crate::{
+    ComponentIdentifier, GetConfig, GetExtensionState, GetInstanceMetadata, GetProperty,
+    InstanceMetadata, InterfaceRequest, LogLevel, Logger, MinifiError,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub trait EnableControllerService {
-    fn enable<Ctx: GetProperty, L: Logger>(context: &Ctx, logger: &L) -> Result<Self, MinifiError>
+    fn enable<Ctx: GetProperty + GetConfig + GetExtensionState + GetInstanceMetadata, L: Logger>(
+        context: &Ctx,
+        logger: &L,
+    ) -> Result<Self, MinifiError>
     where
         Self: Sized;
+
+    /// Called when the agent stops the service, release files, sockets and threads here.
+    /// The implementation is dropped afterwards, a later enable creates a new one
+    fn disable(&mut self) {}
+
+    /// Offers the service interfaces (dyn traits) the service implements,
+    /// so processors can look it up with get_controller_service_dyn
+    fn provide_interfaces<'a>(&'a self, _request: &mut InterfaceRequest<'a>) {}
 }
 
 #[derive(Debug)]
@@ -14,7 +29,9 @@ where
     L: Logger,
 {
     logger: L,
+    metadata: InstanceMetadata,
     enabled_impl: Option<Implementation>,
+    poisoned: AtomicBool,
 }
 
 impl<Implementation, L> ControllerService<Implementation, L>
@@ -25,6 +42,10 @@ where
     pub fn get_implementation(&self) -> Option<&Implementation> {
         self.enabled_impl.as_ref()
     }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_impl.is_some()
+    }
 }
 
 impl<Implementation, L> RawControllerService for ControllerService<Implementation, L>
@@ -34,21 +55,54 @@ where
 {
     type LoggerType = L;
 
-    fn new(logger: Self::LoggerType) -> Self {
+    fn new(metadata: InstanceMetadata, logger: Self::LoggerType) -> Self {
         Self {
             logger,
+            metadata,
             enabled_impl: None,
+            poisoned: AtomicBool::new(false),
         }
     }
 
+    fn instance_metadata(&self) -> &InstanceMetadata {
+        &self.metadata
+    }
+
     fn log(&self, log_level: LogLevel, args: std::fmt::Arguments) {
         self.logger.log(log_level, args);
     }
 
-    fn enable<P: GetProperty>(&mut self, context: &P) -> Result<(), MinifiError> {
+    fn logger(&self) -> &Self::LoggerType {
+        &self.logger
+    }
+
+    fn poison(&self) {
+        self.poisoned.store(true, Ordering::Relaxed);
+    }
+
+    fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Relaxed)
+    }
+
+    fn enable<P: GetProperty + GetConfig + GetExtensionState + GetInstanceMetadata>(
+        &mut self,
+        context: &P,
+    ) -> Result<(), MinifiError> {
         self.enabled_impl = Some(Implementation::enable(context, &self.logger)?);
         Ok(())
     }
+
+    fn provide_interfaces<'a>(&'a self, request: &mut InterfaceRequest<'a>) {
+        if let Some(ref enabled_impl) = self.enabled_impl {
+            enabled_impl.provide_interfaces(request);
+        }
+    }
+
+    fn disable(&mut self) {
+        if let Some(mut enabled_impl) = self.enabled_impl.take() {
+            enabled_impl.disable();
+        }
+    }
 }
 
 impl<Implementation, L> ComponentIdentifier for ControllerService<Implementation, L>
@@ -1,5 +1,7 @@
+use crate::{LogLevel, Logger};
 use minifi_native_sys::MinifiStatus;
 use std::borrow::Cow;
+use std::error::Error;
 use std::ffi::NulError;
 use std::fmt;
 use std::num::{NonZeroU32, ParseIntError};
@@ -16,6 +18,17 @@ pub enum ParseError {
     Other,
 }
 
+/// The error of every fallible framework and component call.
+///
+/// Each variant maps to a fixed MinifiStatus when it reaches the agent:
+/// - StatusError: the status it was created from
+/// - MissingRequiredProperty: MINIFI_STATUS_PROPERTY_NOT_SET
+/// - ValidationError, Parse: MINIFI_STATUS_VALIDATION_FAILED
+/// - WithContext: the status of the wrapped error
+/// - everything else: MINIFI_STATUS_UNKNOWN_ERROR
+///
+/// On the way out the framework wraps the error WithContext (component, phase and the flow file it failed on),
+/// and logs it with its whole source chain the same way for every processor kind and threading model.
 #[derive(Debug)]
 pub enum MinifiError {
     UnknownError,
@@ -27,6 +40,64 @@ pub enum MinifiError {
     TriggerError(Cow<'static, str>),
     Parse(ParseError),
     IoError(std::io::Error),
+    WithContext(Box<ErrorContext>, Box<MinifiError>),
+}
+
+/// The component callback an error happened in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Schedule,
+    Trigger,
+    Enable,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Schedule => write!(f, "on_schedule"),
+            Phase::Trigger => write!(f, "on_trigger"),
+            Phase::Enable => write!(f, "enable"),
+        }
+    }
+}
+
+/// Where an error happened, the innermost (first attached) value wins
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub component: Option<String>,
+    pub phase: Option<Phase>,
+    pub flow_file_uuid: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error")?;
+        if let Some(component) = &self.component {
+            write!(f, " in {component}")?;
+        }
+        if let Some(phase) = &self.phase {
+            write!(f, " during {phase}")?;
+        }
+        if let Some(flow_file_uuid) = &self.flow_file_uuid {
+            write!(f, " on flow file {flow_file_uuid}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays an error followed by its sources, "error: source: source of source"
+pub struct ErrorReport<'a>(&'a dyn Error);
+
+impl fmt::Display for ErrorReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+        let mut source = self.0.source();
+        while let Some(error) = source {
+            write!(f, ": {error}")?;
+            source = error.source();
+        }
+        Ok(())
+    }
 }
 
 impl From<std::io::Error> for MinifiError {
@@ -73,7 +144,81 @@ impl From<NulError> for MinifiError {
 
 impl MinifiError {
     pub(crate) fn to_status(&self) -> MinifiStatus {
-        minifi_native_sys::MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR
+        match self {
+            MinifiError::StatusError((_, code)) => code.get(),
+            MinifiError::MissingRequiredProperty(_) => {
+                minifi_native_sys::MinifiStatus_MINIFI_STATUS_PROPERTY_NOT_SET
+            }
+            MinifiError::ValidationError(_) | MinifiError::Parse(_) => {
+                minifi_native_sys::MinifiStatus_MINIFI_STATUS_VALIDATION_FAILED
+            }
+            MinifiError::WithContext(_, error) => error.to_status(),
+            MinifiError::UnknownError
+            | MinifiError::ControllerServiceError(_)
+            | MinifiError::ScheduleError(_)
+            | MinifiError::TriggerError(_)
+            | MinifiError::IoError(_) => {
+                minifi_native_sys::MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR
+            }
+        }
+    }
+
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            MinifiError::WithContext(context, _) => Some(context),
+            _ => None,
+        }
+    }
+
+    pub fn for_component<S: Into<String>>(self, component: S) -> Self {
+        self.with_context(|context| {
+            context.component.get_or_insert_with(|| component.into());
+        })
+    }
+
+    pub fn in_phase(self, phase: Phase) -> Self {
+        self.with_context(|context| {
+            context.phase.get_or_insert(phase);
+        })
+    }
+
+    pub fn for_flow_file<S: Into<String>>(self, flow_file_uuid: S) -> Self {
+        self.with_context(|context| {
+            context
+                .flow_file_uuid
+                .get_or_insert_with(|| flow_file_uuid.into());
+        })
+    }
+
+    fn with_context<F: FnOnce(&mut ErrorContext)>(self, update: F) -> Self {
+        match self {
+            MinifiError::WithContext(mut context, error) => {
+                update(&mut context);
+                MinifiError::WithContext(context, error)
+            }
+            error => {
+                let mut context = ErrorContext::default();
+                update(&mut context);
+                MinifiError::WithContext(Box::new(context), Box::new(error))
+            }
+        }
+    }
+
+    pub fn report(&self) -> ErrorReport<'_> {
+        ErrorReport(self)
+    }
+
+    /// How every error returned to the agent is reported, the context is attached,
+    /// the error is logged with its sources and mapped to the status
+    pub(crate) fn report_to_agent<L: Logger>(
+        self,
+        component: &str,
+        phase: Phase,
+        logger: &L,
+    ) -> MinifiStatus {
+        let error = self.in_phase(phase).for_component(component);
+        logger.log(LogLevel::Error, format_args!("{}", error.report()));
+        error.to_status()
     }
 
     pub fn validation_err<S: Into<Cow<'static, str>>>(msg: S) -> Self {
@@ -113,7 +258,178 @@ impl fmt::Display for MinifiError {
                 }
                 _ => write!(f, "{} (Unknown Status Code: {})", context, code),
             },
-            _ => write!(f, "{:?}", self),
+            MinifiError::UnknownError => write!(f, "unknown error"),
+            MinifiError::MissingRequiredProperty(property) => {
+                write!(f, "missing required property {property}")
+            }
+            MinifiError::ControllerServiceError(message) => {
+                write!(f, "controller service error: {message}")
+            }
+            MinifiError::ValidationError(message) => write!(f, "validation failed: {message}"),
+            MinifiError::ScheduleError(message) => write!(f, "schedule failed: {message}"),
+            MinifiError::TriggerError(message) => write!(f, "trigger failed: {message}"),
+            MinifiError::Parse(_) => write!(f, "failed to parse value"),
+            MinifiError::IoError(_) => write!(f, "I/O error"),
+            MinifiError::WithContext(context, _) => write!(f, "{context}"),
+        }
+    }
+}
+
+impl Error for MinifiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MinifiError::Parse(error) => Some(error),
+            MinifiError::IoError(error) => Some(error),
+            MinifiError::WithContext(_, error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Strum(_) => write!(f, "invalid enum value"),
+            ParseError::Bool(_) => write!(f, "invalid bool"),
+            ParseError::Int(_) => write!(f, "invalid integer"),
+            ParseError::Duration(_) => write!(f, "invalid duration"),
+            ParseError::Size(_) => write!(f, "invalid data size"),
+            ParseError::Nul(_) => write!(f, "invalid string"),
+            ParseError::Other => write!(f, "invalid value"),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Strum(error) => Some(error),
+            ParseError::Bool(error) => Some(error),
+            ParseError::Int(error) => Some(error),
+            ParseError::Duration(error) => Some(error),
+            ParseError::Size(error) => Some(error),
+            ParseError::Nul(error) => Some(error),
+            ParseError::Other => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockLogger;
+    use minifi_native_sys::*;
+
+    #[test]
+    fn every_variant_maps_to_its_status() {
+        let not_supported = NonZeroU32::new(MinifiStatus_MINIFI_STATUS_NOT_SUPPORTED_PROPERTY);
+        let cases = [
+            (
+                MinifiError::UnknownError,
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+            ),
+            (
+                MinifiError::StatusError(("get".into(), not_supported.unwrap())),
+                MinifiStatus_MINIFI_STATUS_NOT_SUPPORTED_PROPERTY,
+            ),
+            (
+                MinifiError::MissingRequiredProperty("Directory"),
+                MinifiStatus_MINIFI_STATUS_PROPERTY_NOT_SET,
+            ),
+            (
+                MinifiError::ControllerServiceError("missing"),
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+            ),
+            (
+                MinifiError::validation_err("invalid"),
+                MinifiStatus_MINIFI_STATUS_VALIDATION_FAILED,
+            ),
+            (
+                MinifiError::schedule_err("failed"),
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+            ),
+            (
+                MinifiError::trigger_err("failed"),
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+            ),
+            (
+                "maybe".parse::<bool>().unwrap_err().into(),
+                MinifiStatus_MINIFI_STATUS_VALIDATION_FAILED,
+            ),
+            (
+                std::io::Error::other("disk").into(),
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+            ),
+            (
+                MinifiError::MissingRequiredProperty("Directory").in_phase(Phase::Schedule),
+                MinifiStatus_MINIFI_STATUS_PROPERTY_NOT_SET,
+            ),
+        ];
+        for (error, status) in cases {
+            assert_eq!(error.to_status(), status, "{error:?}");
         }
     }
+
+    #[test]
+    fn sources_are_chained() {
+        let error: MinifiError = "12a".parse::<u64>().unwrap_err().into();
+        let parse_error = error.source().unwrap();
+        assert_eq!(parse_error.to_string(), "invalid integer");
+        assert!(parse_error.source().is_some());
+        assert_eq!(
+            error.report().to_string(),
+            "failed to parse value: invalid integer: invalid digit found in string"
+        );
+
+        let error: MinifiError = std::io::Error::other("disk full").into();
+        assert_eq!(error.report().to_string(), "I/O error: disk full");
+    }
+
+    #[test]
+    fn context_is_merged_innermost_first() {
+        let error = MinifiError::trigger_err("boom")
+            .for_flow_file("0000-1111")
+            .in_phase(Phase::Trigger)
+            .for_component("PutFile")
+            .for_flow_file("2222-3333");
+
+        assert_eq!(
+            error.context(),
+            Some(&ErrorContext {
+                component: Some("PutFile".to_string()),
+                phase: Some(Phase::Trigger),
+                flow_file_uuid: Some("0000-1111".to_string()),
+            })
+        );
+        assert!(matches!(
+            error
+                .source()
+                .and_then(|source| source.downcast_ref::<MinifiError>()),
+            Some(MinifiError::TriggerError(_))
+        ));
+        assert_eq!(
+            error.report().to_string(),
+            "Error in PutFile during on_trigger on flow file 0000-1111: trigger failed: boom"
+        );
+    }
+
+    #[test]
+    fn report_to_agent_logs_and_maps() {
+        let logger = MockLogger::new();
+        let status = MinifiError::validation_err("negative size").report_to_agent(
+            "GenerateFlowFile",
+            Phase::Schedule,
+            &logger,
+        );
+
+        assert_eq!(status, MinifiStatus_MINIFI_STATUS_VALIDATION_FAILED);
+        assert_eq!(
+            *logger.logs.lock().unwrap(),
+            vec![(
+                LogLevel::Error,
+                "Error in GenerateFlowFile during on_schedule: validation failed: negative size"
+                    .to_string()
+            )]
+        );
+    }
 }
@@ -0,0 +1,95 @@
+use crate::{MinifiError, PropertyValue};
+use std::collections::BTreeMap;
+
+/// Read access to the agent configuration (minifi.properties) during schedule and enable
+pub trait GetConfig {
+    fn get_config(&self, key: &str) -> Result<Option<String>, MinifiError>;
+
+    /// Parses the value like a property of the same type (e.g. "10 sec" as Duration, "1 MB" as DataSize)
+    fn get_typed_config<T: PropertyValue>(&self, key: &str) -> Result<Option<T>, MinifiError> {
+        self.get_config(key)?
+            .map(|value| T::parse_property_value(&value))
+            .transpose()
+    }
+}
+
+/// The minifi.properties keys listed in declare_minifi_extension!, the agent configuration
+/// can only be read while the extension is initialized so the values are kept for later
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtensionConfig {
+    values: BTreeMap<String, String>,
+}
+
+impl ExtensionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for ExtensionConfig {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self {
+            values: iter
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+}
+
+impl GetConfig for ExtensionConfig {
+    fn get_config(&self, key: &str) -> Result<Option<String>, MinifiError> {
+        Ok(self.values.get(key).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataSize;
+    use std::time::Duration;
+
+    #[test]
+    fn test_typed_config() {
+        let config: ExtensionConfig = [
+            ("nifi.rs.state.directory", "/var/lib/minifi/rs"),
+            ("nifi.rs.flush.period", "10 sec"),
+            ("nifi.rs.buffer.size", "1 kB"),
+            ("nifi.rs.enabled", "yes"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            config.get_config("nifi.rs.state.directory").unwrap(),
+            Some("/var/lib/minifi/rs".to_string())
+        );
+        assert_eq!(
+            config
+                .get_typed_config::<Duration>("nifi.rs.flush.period")
+                .unwrap(),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            config
+                .get_typed_config::<DataSize>("nifi.rs.buffer.size")
+                .unwrap()
+                .map(|size| size.as_u64()),
+            Some(1000)
+        );
+        assert!(config.get_typed_config::<bool>("nifi.rs.enabled").is_err());
+        assert_eq!(config.get_typed_config::<u64>("missing").unwrap(), None);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::{ExtensionConfig, MinifiError};
+use std::sync::Arc;
+
+/// Extension-wide state (e.g. a shared runtime, connection pool or cache) declared with
+/// `state: MyState` in declare_minifi_extension!, it is created when the agent loads the extension
+/// and torn down when the agent unloads it
+pub trait ExtensionState: Send + Sync + Sized + 'static {
+    /// Called once while the extension is initialized, config has the declared config_keys,
+    /// an error fails the initialization so the agent doesn't load the extension
+    fn init(config: &ExtensionConfig) -> Result<Self, MinifiError>;
+
+    /// Called when the agent unloads the extension, after every processor and controller service is destroyed,
+    /// even if a clone of the state is still held (e.g. by a thread the extension didn't join)
+    fn deinit(&self) {}
+}
+
+/// Access to the state of the extension during schedule and enable
+pub trait GetExtensionState {
+    /// None if the extension has no state of this type
+    fn get_extension_state<S: ExtensionState>(&self) -> Option<Arc<S>>;
+}
@@ -1 +1,58 @@
-pub trait FlowFile {}
+use crate::MinifiError;
+use std::time::SystemTime;
+
+/// Names of the core attributes every flow file has
+pub mod core_attributes {
+    pub const UUID: &str = "uuid";
+    pub const FILENAME: &str = "filename";
+    pub const PATH: &str = "path";
+    pub const ABSOLUTE_PATH: &str = "absolute.path";
+    pub const MIME_TYPE: &str = "mime.type";
+    pub const ENTRY_DATE: &str = "entryDate";
+    pub const LINEAGE_START_DATE: &str = "lineageStartDate";
+    pub const FILE_SIZE: &str = "fileSize";
+}
+
+/// Typed access to the core attributes, other attributes are available through attribute or the session
+pub trait FlowFile {
+    fn attribute(&self, name: &str) -> Option<String>;
+
+    fn uuid(&self) -> Option<String> {
+        self.attribute(core_attributes::UUID)
+    }
+
+    fn filename(&self) -> Option<String> {
+        self.attribute(core_attributes::FILENAME)
+    }
+
+    fn path(&self) -> Option<String> {
+        self.attribute(core_attributes::PATH)
+    }
+
+    fn absolute_path(&self) -> Option<String> {
+        self.attribute(core_attributes::ABSOLUTE_PATH)
+    }
+
+    fn mime_type(&self) -> Option<String> {
+        self.attribute(core_attributes::MIME_TYPE)
+    }
+
+    /// When the flow file entered the flow
+    ///
+    /// Unsupported on the agent: the C API doesn't expose it, so CffiFlowFile always returns None,
+    /// only the mock flow file provides it.
+    fn entry_date(&self) -> Option<SystemTime> {
+        None
+    }
+
+    /// When the oldest ancestor of the flow file entered the flow
+    ///
+    /// Unsupported on the agent: the C API doesn't expose it, so CffiFlowFile always returns None,
+    /// only the mock flow file provides it.
+    fn lineage_start_date(&self) -> Option<SystemTime> {
+        None
+    }
+
+    /// Content size in bytes
+    fn size(&self) -> Result<u64, MinifiError>;
+}
@@ -0,0 +1,202 @@
+use std::fmt::Debug;
+use std::io::{BufRead, BufReader, Cursor, Error, ErrorKind, Read, Seek, SeekFrom};
+
+/// Content of a flow file, read sequentially
+///
+/// Flow file content always knows its size, random access depends on the content repository,
+/// [`seekable`](dyn InputStream::seekable) falls back to buffering the content in memory.
+pub trait InputStream: BufRead + Send + Debug {
+    /// Size of the whole content in bytes (not only the remaining part), None if unknown
+    fn size(&self) -> Option<u64>;
+
+    /// Random access without buffering, None if the content cannot seek natively
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream>;
+}
+
+pub trait SeekableInputStream: InputStream + Seek {}
+
+impl<T: InputStream + Seek> SeekableInputStream for T {}
+
+impl dyn InputStream + '_ {
+    /// Random access to the content, natively if the stream supports it,
+    /// otherwise the remaining content is buffered and positions before it cannot be reached
+    pub fn seekable(&mut self) -> std::io::Result<SeekableStream<'_>> {
+        if self.as_seekable().is_some() {
+            let native = self
+                .as_seekable()
+                .expect("as_seekable was checked right before");
+            return Ok(SeekableStream::Native(native));
+        }
+        let mut remaining = Vec::new();
+        self.read_to_end(&mut remaining)?;
+        let offset = self
+            .size()
+            .map_or(0, |size| size.saturating_sub(remaining.len() as u64));
+        Ok(SeekableStream::Buffered {
+            offset,
+            content: Cursor::new(remaining),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum SeekableStream<'a> {
+    Native(&'a mut dyn SeekableInputStream),
+    /// The content after `offset` read into memory
+    Buffered {
+        offset: u64,
+        content: Cursor<Vec<u8>>,
+    },
+}
+
+impl Read for SeekableStream<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            SeekableStream::Native(stream) => stream.read(buf),
+            SeekableStream::Buffered { content, .. } => content.read(buf),
+        }
+    }
+}
+
+impl BufRead for SeekableStream<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        match self {
+            SeekableStream::Native(stream) => stream.fill_buf(),
+            SeekableStream::Buffered { content, .. } => content.fill_buf(),
+        }
+    }
+
+    fn consume(&mut self, amount: usize) {
+        match self {
+            SeekableStream::Native(stream) => stream.consume(amount),
+            SeekableStream::Buffered { content, .. } => content.consume(amount),
+        }
+    }
+}
+
+impl Seek for SeekableStream<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        match self {
+            SeekableStream::Native(stream) => stream.seek(pos),
+            SeekableStream::Buffered { offset, content } => {
+                let pos = match pos {
+                    SeekFrom::Start(position) => {
+                        SeekFrom::Start(position.checked_sub(*offset).ok_or_else(|| {
+                            Error::new(
+                                ErrorKind::InvalidInput,
+                                format!("cannot seek to {position}, the content was buffered from {offset}"),
+                            )
+                        })?)
+                    }
+                    relative => relative,
+                };
+                Ok(*offset + content.seek(pos)?)
+            }
+        }
+    }
+}
+
+impl InputStream for SeekableStream<'_> {
+    fn size(&self) -> Option<u64> {
+        match self {
+            SeekableStream::Native(stream) => stream.size(),
+            SeekableStream::Buffered { offset, content } => {
+                Some(*offset + content.get_ref().len() as u64)
+            }
+        }
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        Some(self)
+    }
+}
+
+impl<T: AsRef<[u8]> + Send + Debug> InputStream for Cursor<T> {
+    fn size(&self) -> Option<u64> {
+        Some(self.get_ref().as_ref().len() as u64)
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        Some(self)
+    }
+}
+
+// the slice only knows its remaining part, not the whole size
+impl InputStream for &[u8] {
+    fn size(&self) -> Option<u64> {
+        None
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        None
+    }
+}
+
+impl<R: Read + Send + Debug> InputStream for BufReader<R> {
+    fn size(&self) -> Option<u64> {
+        None
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        None
+    }
+}
+
+impl<T: InputStream + ?Sized> InputStream for &mut T {
+    fn size(&self) -> Option<u64> {
+        (**self).size()
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        (**self).as_seekable()
+    }
+}
+
+impl<T: InputStream + ?Sized> InputStream for Box<T> {
+    fn size(&self) -> Option<u64> {
+        (**self).size()
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        (**self).as_seekable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn native_seek() {
+        let mut cursor = Cursor::new(b"header|body|trailer".to_vec());
+        let input_stream: &mut dyn InputStream = &mut cursor;
+        assert_eq!(input_stream.size(), Some(19));
+
+        let mut seekable = input_stream.seekable().unwrap();
+        assert!(matches!(seekable, SeekableStream::Native(_)));
+        seekable.seek(SeekFrom::End(-7)).unwrap();
+        let mut trailer = String::new();
+        seekable.read_to_string(&mut trailer).unwrap();
+        assert_eq!(trailer, "trailer");
+    }
+
+    #[test]
+    fn buffered_fallback_without_size() {
+        let mut content: &[u8] = b"header|body|trailer";
+        let input_stream: &mut dyn InputStream = &mut content;
+        let mut header = [0u8; 7];
+        input_stream.read_exact(&mut header).unwrap();
+
+        // positions are relative to the buffered part when the size is unknown
+        let mut seekable = input_stream.seekable().unwrap();
+        assert!(matches!(
+            seekable,
+            SeekableStream::Buffered { offset: 0, .. }
+        ));
+        assert_eq!(seekable.size(), Some(12));
+        assert_eq!(seekable.seek(SeekFrom::End(-7)).unwrap(), 5);
+        let mut trailer = String::new();
+        seekable.read_to_string(&mut trailer).unwrap();
+        assert_eq!(trailer, "trailer");
+    }
+}
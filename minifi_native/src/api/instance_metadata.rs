@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+/// Identifies one instance of a processor or controller service in the flow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceMetadata {
+    uuid: Arc<str>,
+    name: Arc<str>,
+}
+
+impl InstanceMetadata {
+    pub fn new(uuid: &str, name: &str) -> Self {
+        Self {
+            uuid: uuid.into(),
+            name: name.into(),
+        }
+    }
+
+    /// The uuid assigned by the agent, stable across restarts
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    /// The user given name of the instance
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Default for InstanceMetadata {
+    fn default() -> Self {
+        Self::new("00000000-0000-0000-0000-000000000000", "")
+    }
+}
+
+/// Access to the metadata of the processor or controller service instance
+pub trait GetInstanceMetadata {
+    fn instance_metadata(&self) -> &InstanceMetadata;
+}
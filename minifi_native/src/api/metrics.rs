@@ -0,0 +1,225 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A group of metrics that can be published, usually implemented with #[derive(Metrics)]
+///
+/// ```ignore
+/// #[derive(Debug, Default, Metrics)]
+/// struct GetFileMetrics {
+///     accepted_files: Counter, // published as AcceptedFiles
+///     #[metric(name = "ListingSize")]
+///     listed_paths: Gauge,
+///     file_sizes: Histogram, // FileSizesCount, FileSizesSum, FileSizesP50, FileSizesP90, FileSizesP99
+/// }
+///
+/// impl CalculateMetrics for GetFileRs {
+///     fn calculate_metrics(&self) -> Vec<(String, f64)> {
+///         self.metrics.collect()
+///     }
+/// }
+/// ```
+pub trait Metrics {
+    fn collect_into(&self, metrics: &mut Vec<(String, f64)>);
+
+    fn collect(&self) -> Vec<(String, f64)> {
+        let mut metrics = Vec::new();
+        self.collect_into(&mut metrics);
+        metrics
+    }
+}
+
+/// A single lock-free metric that publishes one or more values under its name
+pub trait Metric {
+    fn publish(&self, name: &str, metrics: &mut Vec<(String, f64)>);
+}
+
+/// Monotonically increasing count
+#[derive(Debug, Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    pub const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    pub fn increment(&self) {
+        self.add(1);
+    }
+
+    pub fn add(&self, amount: u64) {
+        self.0.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl Metric for Counter {
+    fn publish(&self, name: &str, metrics: &mut Vec<(String, f64)>) {
+        metrics.push((name.to_string(), self.get() as f64));
+    }
+}
+
+/// The last value that was set
+#[derive(Debug, Default)]
+pub struct Gauge(AtomicU64);
+
+impl Gauge {
+    pub const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    pub fn set(&self, value: f64) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+impl Metric for Gauge {
+    fn publish(&self, name: &str, metrics: &mut Vec<(String, f64)>) {
+        metrics.push((name.to_string(), self.get()));
+    }
+}
+
+// bucket 0 holds zeros, bucket i the values in [2^(i-1), 2^i)
+const HISTOGRAM_BUCKETS: usize = u64::BITS as usize + 1;
+
+/// Distribution of the recorded values in power of two buckets,
+/// the published percentiles are the upper bounds of the buckets they fall into
+#[derive(Debug)]
+pub struct Histogram {
+    buckets: [AtomicU64; HISTOGRAM_BUCKETS],
+    count: AtomicU64,
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, value: u64) {
+        let bucket = (u64::BITS - value.leading_zeros()) as usize;
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum(&self) -> u64 {
+        self.sum.load(Ordering::Relaxed)
+    }
+
+    /// Upper bound of the bucket the percentile (0-100) falls into, 0 if nothing was recorded
+    pub fn percentile(&self, percentile: u8) -> u64 {
+        let counts: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        let total: u64 = counts.iter().sum();
+        let rank = (total * u64::from(percentile.min(100)))
+            .div_ceil(100)
+            .max(1);
+        let mut seen = 0;
+        for (bucket, count) in counts.into_iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_upper_bound(bucket);
+            }
+        }
+        0
+    }
+}
+
+fn bucket_upper_bound(bucket: usize) -> u64 {
+    match bucket {
+        0 => 0,
+        _ => u64::MAX >> (u64::BITS as usize - bucket),
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metric for Histogram {
+    fn publish(&self, name: &str, metrics: &mut Vec<(String, f64)>) {
+        metrics.push((format!("{name}Count"), self.count() as f64));
+        metrics.push((format!("{name}Sum"), self.sum() as f64));
+        for percentile in [50, 90, 99] {
+            metrics.push((
+                format!("{name}P{percentile}"),
+                self.percentile(percentile) as f64,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_and_gauge() {
+        let counter = Counter::new();
+        counter.increment();
+        counter.add(41);
+        let gauge = Gauge::new();
+        gauge.set(0.5);
+
+        let mut metrics = Vec::new();
+        counter.publish("Answers", &mut metrics);
+        gauge.publish("Ratio", &mut metrics);
+        assert_eq!(
+            metrics,
+            vec![("Answers".to_string(), 42.0), ("Ratio".to_string(), 0.5)]
+        );
+    }
+
+    #[test]
+    fn histogram_percentiles() {
+        let histogram = Histogram::new();
+        assert_eq!(histogram.percentile(50), 0);
+
+        for value in 1..=100 {
+            histogram.record(value);
+        }
+        histogram.record(0);
+        assert_eq!(histogram.count(), 101);
+        assert_eq!(histogram.sum(), 5050);
+        assert_eq!(histogram.percentile(0), 0);
+        assert_eq!(histogram.percentile(50), 63);
+        assert_eq!(histogram.percentile(99), 127);
+
+        histogram.record(u64::MAX);
+        assert_eq!(histogram.percentile(100), u64::MAX);
+    }
+
+    #[test]
+    fn histogram_publishes_summary() {
+        let histogram = Histogram::new();
+        histogram.record(3);
+
+        let mut metrics = Vec::new();
+        histogram.publish("Size", &mut metrics);
+        let names: Vec<&str> = metrics.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["SizeCount", "SizeSum", "SizeP50", "SizeP90", "SizeP99"]
+        );
+        assert_eq!(metrics[2].1, 3.0);
+    }
+}
@@ -1,13 +1,17 @@
 use crate::StandardPropertyValidator::*;
 use crate::api::RawControllerService;
 use crate::api::component_definition_traits::ComponentIdentifier;
+use crate::api::extension_config::GetConfig;
+use crate::api::extension_state::GetExtensionState;
 use crate::api::flow_file::FlowFile;
-use crate::api::property::GetControllerService;
+use crate::api::instance_metadata::GetInstanceMetadata;
+use crate::api::property::{GetControllerService, parse_enum_property};
 use crate::{EnableControllerService, GetProperty, MinifiError, Property};
 use std::str::FromStr;
 use std::time::Duration;
+use strum::VariantNames;
 
-pub trait ProcessContext {
+pub trait ProcessContext: GetConfig + GetExtensionState + GetInstanceMetadata {
     type FlowFile: FlowFile;
 
     fn get_property(
@@ -90,6 +94,22 @@ pub trait ProcessContext {
         }
     }
 
+    fn get_enum_property<E: FromStr + VariantNames>(
+        &self,
+        property: &Property,
+        flow_file: Option<&Self::FlowFile>,
+    ) -> Result<Option<E>, MinifiError> {
+        parse_enum_property(property, self.get_property(property, flow_file)?)
+    }
+
+    /// Value of a dynamic property, expression language is evaluated against the flow file.
+    /// The agent's C API cannot list the dynamic properties set on the processor, so they are looked up by name.
+    fn get_dynamic_property(
+        &self,
+        name: &str,
+        flow_file: Option<&Self::FlowFile>,
+    ) -> Result<Option<String>, MinifiError>;
+
     fn get_raw_controller_service<Cs>(
         &self,
         property: &Property,
@@ -100,6 +120,10 @@ pub trait ProcessContext {
     fn get_controller_service<Cs>(&self, property: &Property) -> Result<Option<&Cs>, MinifiError>
     where
         Cs: EnableControllerService + ComponentIdentifier + 'static;
+
+    fn get_controller_service_dyn<I>(&self, property: &Property) -> Result<Option<&I>, MinifiError>
+    where
+        I: ?Sized + 'static;
 }
 
 impl<S> GetProperty for S
@@ -121,4 +145,11 @@ where
     {
         self.get_controller_service(property)
     }
+
+    fn get_controller_service_dyn<I>(&self, property: &Property) -> Result<Option<&I>, MinifiError>
+    where
+        I: ?Sized + 'static,
+    {
+        self.get_controller_service_dyn(property)
+    }
 }
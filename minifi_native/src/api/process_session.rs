@@ -1,9 +1,10 @@
 use crate::MinifiError;
 use crate::api::flow_file::FlowFile;
-pub trait InputStream: std::io::BufRead + Send + std::fmt::Debug {}
+pub use crate::api::input_stream::InputStream;
+use regex::Regex;
+use std::collections::HashMap;
 pub trait OutputStream: std::io::Write + Send + std::fmt::Debug {}
 impl<T: std::io::Write + Send + std::fmt::Debug> OutputStream for T {}
-impl<T: std::io::BufRead + Send + std::fmt::Debug> InputStream for T {}
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum IoState {
@@ -15,6 +16,22 @@ pub trait ProcessSession {
     type FlowFile: FlowFile;
 
     fn create(&mut self) -> Result<Self::FlowFile, MinifiError>;
+    /// Creates a flow file that inherits the attributes and the lineage of the parent
+    fn create_child(&mut self, parent: &Self::FlowFile) -> Result<Self::FlowFile, MinifiError>;
+    /// Creates a child of the flow file with the same content
+    fn clone_flow_file(
+        &mut self,
+        flow_file: &Self::FlowFile,
+    ) -> Result<Self::FlowFile, MinifiError> {
+        let clone = self.create_child(flow_file)?;
+        self.read_stream(flow_file, |input_stream| {
+            self.write_stream(&clone, |output_stream| {
+                std::io::copy(input_stream, output_stream)?;
+                Ok(((), IoState::Ok))
+            })
+        })?;
+        Ok(clone)
+    }
     fn get(&mut self) -> Option<Self::FlowFile>;
     fn transfer(&self, flow_file: Self::FlowFile, relationship: &str) -> Result<(), MinifiError>;
     fn remove(&mut self, flow_file: Self::FlowFile) -> Result<(), MinifiError>;
@@ -25,6 +42,11 @@ pub trait ProcessSession {
         attr_key: &str,
         attr_value: &str,
     ) -> Result<(), MinifiError>;
+    fn remove_attribute(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        attr_key: &str,
+    ) -> Result<(), MinifiError>;
     fn get_attribute(&self, flow_file: &Self::FlowFile, attr_key: &str) -> Option<String>;
     fn on_attributes<F: FnMut(&str, &str)>(
         &self,
@@ -32,17 +54,108 @@ pub trait ProcessSession {
         process_attr: F,
     ) -> bool;
 
+    fn get_attributes(&self, flow_file: &Self::FlowFile) -> HashMap<String, String> {
+        let mut attributes = HashMap::new();
+        self.on_attributes(flow_file, |attr_key, attr_value| {
+            attributes.insert(attr_key.to_string(), attr_value.to_string());
+        });
+        attributes
+    }
+
+    fn set_attributes<I, K, V>(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        attributes: I,
+    ) -> Result<(), MinifiError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (attr_key, attr_value) in attributes {
+            self.set_attribute(flow_file, attr_key.as_ref(), attr_value.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Removes every attribute whose whole name matches the regex e.g. `http\..*`,
+    /// returns how many were removed
+    fn remove_attributes_matching(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        pattern: &str,
+    ) -> Result<usize, MinifiError> {
+        let pattern = Regex::new(&format!("^(?:{pattern})$")).map_err(|err| {
+            MinifiError::ValidationError(format!("Invalid attribute pattern: {err}").into())
+        })?;
+        self.remove_attributes_where(flow_file, |attr_key| pattern.is_match(attr_key))
+    }
+
+    /// Removes every attribute whose name matches the predicate e.g. `|key| key.starts_with("http.")`,
+    /// returns how many were removed
+    fn remove_attributes_where<P: FnMut(&str) -> bool>(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        mut predicate: P,
+    ) -> Result<usize, MinifiError> {
+        let mut matching_keys = Vec::new();
+        self.on_attributes(flow_file, |attr_key, _| {
+            if predicate(attr_key) {
+                matching_keys.push(attr_key.to_string());
+            }
+        });
+        for attr_key in &matching_keys {
+            self.remove_attribute(flow_file, attr_key)?;
+        }
+        Ok(matching_keys.len())
+    }
+
     fn write(&self, flow_file: &Self::FlowFile, data: &[u8]) -> Result<(), MinifiError>;
     fn write_lazy<'a>(
         &self,
         flow_file: &Self::FlowFile,
         stream: Box<dyn std::io::Read + 'a>,
     ) -> Result<(), MinifiError>;
+    /// Writes the slices in order as the new content straight from the caller's memory,
+    /// in writes of at most chunk_size bytes, 0 writes every slice at once
+    fn write_vectored(
+        &self,
+        flow_file: &Self::FlowFile,
+        slices: &[&[u8]],
+        chunk_size: usize,
+    ) -> Result<(), MinifiError>;
 
     fn write_stream<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
     where
         F: FnOnce(&mut dyn OutputStream) -> Result<(R, IoState), MinifiError>;
 
+    /// Writes after the existing content, IoState::Cancel keeps the content unchanged
+    ///
+    /// The default streams the old content into the new one through modify
+    fn append<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
+    where
+        F: FnOnce(&mut dyn OutputStream) -> Result<(R, IoState), MinifiError>,
+    {
+        self.modify(flow_file, |input_stream, output_stream| {
+            std::io::copy(input_stream, output_stream)?;
+            callback(output_stream)
+        })
+    }
+
+    /// Replaces the content while the old content is still readable, in a single pass without a temporary copy
+    ///
+    /// The default reads and writes the flow file at the same time, as the agent has no native modify
+    fn modify<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
+    where
+        F: FnOnce(&mut dyn InputStream, &mut dyn OutputStream) -> Result<(R, IoState), MinifiError>,
+    {
+        self.read_stream(flow_file, |input_stream| {
+            self.write_stream(flow_file, |output_stream| {
+                callback(input_stream, output_stream)
+            })
+        })
+    }
+
     fn read(&self, flow_file: &Self::FlowFile) -> Option<Vec<u8>>;
     fn read_stream<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
     where
@@ -1,9 +1,16 @@
+use crate::StandardMetrics;
+use crate::api::processor_metrics::ProcessorMetrics;
 use crate::api::{RawProcessor, ThreadingModel};
-use crate::{GetProperty, LogLevel, Logger, MinifiError, ProcessContext};
+use crate::{
+    GetConfig, GetExtensionState, GetInstanceMetadata, GetProperty, InstanceMetadata, LogLevel,
+    Logger, MinifiError, ProcessContext,
+};
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub trait Schedule {
-    fn schedule<Ctx: GetProperty, L: Logger>(
+    fn schedule<Ctx: GetProperty + GetConfig + GetExtensionState + GetInstanceMetadata, L: Logger>(
         context: &Ctx,
         logger: &L,
     ) -> Result<Self, MinifiError>
@@ -15,6 +22,9 @@ pub trait Schedule {
 
 /// Custom metrics for the processor, the default implementation can be derived #[derive(DefaultMetrics)]
 pub trait CalculateMetrics {
+    /// The framework metrics published before the custom ones
+    const STANDARD_METRICS: StandardMetrics = StandardMetrics::ALL;
+
     fn calculate_metrics(&self) -> Vec<(String, f64)> {
         vec![]
     }
@@ -22,7 +32,6 @@ pub trait CalculateMetrics {
 
 /// Rarely used processor features, the default implementation can be derived #[derive(NoAdvancedProcessorFeatures)]
 pub trait AdvancedProcessorFeatures {
-    fn restore(&self) -> bool;
     fn get_trigger_when_empty(&self) -> bool;
     fn is_work_available(&self) -> bool;
 }
@@ -34,6 +43,9 @@ where
     L: Logger,
 {
     pub(crate) logger: L,
+    metadata: InstanceMetadata,
+    metrics: Arc<ProcessorMetrics>,
+    poisoned: AtomicBool,
     pub(crate) scheduled_impl: Option<Impl>,
     threading_model: PhantomData<T>,
     flow_file_type: PhantomData<Kind>,
@@ -48,20 +60,20 @@ where
     type Threading = T;
     type LoggerType = L;
 
-    fn new(logger: Self::LoggerType) -> Self {
+    fn new(metadata: InstanceMetadata, logger: Self::LoggerType) -> Self {
         Self {
             logger,
+            metadata,
+            metrics: Arc::new(ProcessorMetrics::new(Impl::STANDARD_METRICS)),
+            poisoned: AtomicBool::new(false),
             scheduled_impl: None,
             threading_model: PhantomData,
             flow_file_type: PhantomData,
         }
     }
 
-    fn restore(&self) -> bool {
-        self.scheduled_impl
-            .as_ref()
-            .and_then(|i| Some(i.restore()))
-            .unwrap_or(false)
+    fn instance_metadata(&self) -> &InstanceMetadata {
+        &self.metadata
     }
 
     fn get_trigger_when_empty(&self) -> bool {
@@ -82,6 +94,18 @@ where
         self.logger.log(log_level, args);
     }
 
+    fn logger(&self) -> &Self::LoggerType {
+        &self.logger
+    }
+
+    fn poison(&self) {
+        self.poisoned.store(true, Ordering::Relaxed);
+    }
+
+    fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Relaxed)
+    }
+
     fn on_schedule<P: ProcessContext>(&mut self, context: &P) -> Result<(), MinifiError> {
         self.scheduled_impl = Some(Impl::schedule(context, &self.logger)?);
         Ok(())
@@ -94,11 +118,15 @@ where
     }
 
     fn calculate_metrics(&self) -> Vec<(String, f64)> {
+        let mut metrics = self.metrics.calculate_metrics();
         if let Some(ref scheduled_impl) = self.scheduled_impl {
-            scheduled_impl.calculate_metrics()
-        } else {
-            // this seems to normal so no need for warnings
-            vec![]
+            metrics.extend(scheduled_impl.calculate_metrics());
         }
+        // an unscheduled processor seems to normal so no need for warnings
+        metrics
+    }
+
+    fn processor_metrics(&self) -> &Arc<ProcessorMetrics> {
+        &self.metrics
     }
 }
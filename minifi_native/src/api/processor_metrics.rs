@@ -0,0 +1,682 @@
+use crate::api::process_session::IoState;
+use crate::{
+    Counter, Histogram, InputStream, Metric, MinifiError, OnTriggerResult, OutputStream,
+    ProcessSession, SeekableInputStream,
+};
+use std::cell::{Cell, RefCell};
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// The metrics every processor publishes next to its own CalculateMetrics,
+/// override CalculateMetrics::STANDARD_METRICS to opt out e.g. `StandardMetrics::ALL.without(StandardMetrics::LATENCY)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StandardMetrics(u8);
+
+impl StandardMetrics {
+    pub const NONE: StandardMetrics = StandardMetrics(0);
+    /// OnTriggerInvocations
+    pub const TRIGGERS: StandardMetrics = StandardMetrics(1);
+    /// YieldedTriggers
+    pub const YIELDS: StandardMetrics = StandardMetrics(1 << 1);
+    /// FailedTriggers
+    pub const ERRORS: StandardMetrics = StandardMetrics(1 << 2);
+    /// IncomingFlowFiles
+    pub const FLOW_FILES_IN: StandardMetrics = StandardMetrics(1 << 3);
+    /// TransferredFlowFiles.<relationship>, TransferredFlowFilesToOtherRelationships past the 32nd relationship
+    pub const FLOW_FILES_OUT: StandardMetrics = StandardMetrics(1 << 4);
+    /// BytesRead
+    pub const BYTES_READ: StandardMetrics = StandardMetrics(1 << 5);
+    /// BytesWritten
+    pub const BYTES_WRITTEN: StandardMetrics = StandardMetrics(1 << 6);
+    /// OnTriggerLatencyP50Ms, OnTriggerLatencyP90Ms and OnTriggerLatencyP99Ms, rounded up to a power of two microseconds
+    pub const LATENCY: StandardMetrics = StandardMetrics(1 << 7);
+    pub const ALL: StandardMetrics = StandardMetrics(u8::MAX);
+
+    pub const fn with(self, other: StandardMetrics) -> Self {
+        StandardMetrics(self.0 | other.0)
+    }
+
+    pub const fn without(self, other: StandardMetrics) -> Self {
+        StandardMetrics(self.0 & !other.0)
+    }
+
+    pub const fn contains(self, other: StandardMetrics) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for StandardMetrics {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+// relationships get a transfer counter in the order they are first transferred to,
+// the ones beyond the slots are only counted in TransferredFlowFilesToOtherRelationships
+const RELATIONSHIP_SLOTS: usize = 32;
+
+/// Lock-free transfer counts per relationship, a slot is claimed by the first transfer to its relationship
+#[derive(Debug)]
+struct TransferCounters {
+    slots: [(OnceLock<String>, Counter); RELATIONSHIP_SLOTS],
+    other: Counter,
+}
+
+impl TransferCounters {
+    fn new() -> Self {
+        Self {
+            slots: std::array::from_fn(|_| (OnceLock::new(), Counter::new())),
+            other: Counter::new(),
+        }
+    }
+
+    fn add(&self, relationship: &str, amount: u64) {
+        for (name, counter) in &self.slots {
+            // a concurrent trigger may claim the free slot for another relationship first
+            if name.get_or_init(|| relationship.to_string()) == relationship {
+                counter.add(amount);
+                return;
+            }
+        }
+        self.other.add(amount);
+    }
+
+    fn publish(&self, metrics: &mut Vec<(String, f64)>) {
+        for (name, counter) in &self.slots {
+            let Some(name) = name.get() else {
+                break;
+            };
+            counter.publish(&format!("TransferredFlowFiles.{name}"), metrics);
+        }
+        if self.other.get() > 0 {
+            self.other
+                .publish("TransferredFlowFilesToOtherRelationships", metrics);
+        }
+    }
+}
+
+/// The standard metrics of one processor instance, shared by its concurrent triggers
+#[derive(Debug)]
+pub struct ProcessorMetrics {
+    enabled: StandardMetrics,
+    triggers: Counter,
+    yields: Counter,
+    errors: Counter,
+    flow_files_in: Counter,
+    bytes_read: Counter,
+    bytes_written: Counter,
+    flow_files_out: TransferCounters,
+    // on_trigger latencies in microseconds
+    latencies: Histogram,
+}
+
+impl ProcessorMetrics {
+    pub(crate) fn new(enabled: StandardMetrics) -> Self {
+        Self {
+            enabled,
+            triggers: Counter::new(),
+            yields: Counter::new(),
+            errors: Counter::new(),
+            flow_files_in: Counter::new(),
+            bytes_read: Counter::new(),
+            bytes_written: Counter::new(),
+            flow_files_out: TransferCounters::new(),
+            latencies: Histogram::new(),
+        }
+    }
+
+    fn record_trigger(
+        &self,
+        result: &Result<OnTriggerResult, MinifiError>,
+        counts: TriggerCounts,
+        latency: Duration,
+    ) {
+        self.triggers.increment();
+        match result {
+            Ok(OnTriggerResult::Ok) => {}
+            Ok(OnTriggerResult::Yield) => {
+                self.yields.increment();
+            }
+            Err(_) => {
+                self.errors.increment();
+            }
+        }
+        // a failed trigger is rolled back, nothing it read or transferred counts
+        if result.is_ok() {
+            self.flow_files_in.add(counts.flow_files_in.get());
+            self.bytes_read.add(counts.bytes_read.get());
+            self.bytes_written.add(counts.bytes_written.get());
+            for (relationship, count) in counts.flow_files_out.into_inner() {
+                self.flow_files_out.add(&relationship, count);
+            }
+        }
+        if self.enabled.contains(StandardMetrics::LATENCY) {
+            self.latencies
+                .record(u64::try_from(latency.as_micros()).unwrap_or(u64::MAX));
+        }
+    }
+
+    pub(crate) fn calculate_metrics(&self) -> Vec<(String, f64)> {
+        let mut metrics = Vec::new();
+        let counters = [
+            (
+                StandardMetrics::TRIGGERS,
+                "OnTriggerInvocations",
+                &self.triggers,
+            ),
+            (StandardMetrics::YIELDS, "YieldedTriggers", &self.yields),
+            (StandardMetrics::ERRORS, "FailedTriggers", &self.errors),
+            (
+                StandardMetrics::FLOW_FILES_IN,
+                "IncomingFlowFiles",
+                &self.flow_files_in,
+            ),
+            (StandardMetrics::BYTES_READ, "BytesRead", &self.bytes_read),
+            (
+                StandardMetrics::BYTES_WRITTEN,
+                "BytesWritten",
+                &self.bytes_written,
+            ),
+        ];
+        for (metric, name, counter) in counters {
+            if self.enabled.contains(metric) {
+                counter.publish(name, &mut metrics);
+            }
+        }
+
+        if self.enabled.contains(StandardMetrics::FLOW_FILES_OUT) {
+            self.flow_files_out.publish(&mut metrics);
+        }
+
+        if self.enabled.contains(StandardMetrics::LATENCY) {
+            for (percentile, name) in [
+                (50, "OnTriggerLatencyP50Ms"),
+                (90, "OnTriggerLatencyP90Ms"),
+                (99, "OnTriggerLatencyP99Ms"),
+            ] {
+                let latency_micros = self.latencies.percentile(percentile);
+                metrics.push((name.to_string(), latency_micros as f64 / 1000.0));
+            }
+        }
+        metrics
+    }
+}
+
+/// Runs on_trigger with a session that counts what the trigger reads, writes and transfers
+pub(crate) fn measured_on_trigger<PS, F>(
+    metrics: &ProcessorMetrics,
+    session: &mut PS,
+    on_trigger: F,
+) -> Result<OnTriggerResult, MinifiError>
+where
+    PS: ProcessSession,
+    F: FnOnce(&mut MeteredSession<'_, PS>) -> Result<OnTriggerResult, MinifiError>,
+{
+    let started = Instant::now();
+    let mut metered_session = MeteredSession {
+        session,
+        enabled: metrics.enabled,
+        counts: TriggerCounts::default(),
+    };
+    let result = on_trigger(&mut metered_session);
+    metrics.record_trigger(&result, metered_session.counts, started.elapsed());
+    result
+}
+
+#[derive(Debug, Default)]
+struct TriggerCounts {
+    flow_files_in: Cell<u64>,
+    bytes_read: Cell<u64>,
+    bytes_written: Cell<u64>,
+    flow_files_out: RefCell<Vec<(String, u64)>>,
+}
+
+/// Forwards everything to the real session while counting for the standard metrics
+pub(crate) struct MeteredSession<'s, PS: ProcessSession> {
+    session: &'s mut PS,
+    enabled: StandardMetrics,
+    counts: TriggerCounts,
+}
+
+impl<'s, PS: ProcessSession> MeteredSession<'s, PS> {
+    fn count(&self, metric: StandardMetrics, counter: &Cell<u64>, amount: u64) {
+        if self.enabled.contains(metric) {
+            counter.set(counter.get() + amount);
+        }
+    }
+
+    fn count_flow_file_in(&self) {
+        self.count(
+            StandardMetrics::FLOW_FILES_IN,
+            &self.counts.flow_files_in,
+            1,
+        );
+    }
+
+    fn count_bytes_read(&self, amount: u64) {
+        self.count(StandardMetrics::BYTES_READ, &self.counts.bytes_read, amount);
+    }
+
+    fn count_bytes_written(&self, amount: u64) {
+        self.count(
+            StandardMetrics::BYTES_WRITTEN,
+            &self.counts.bytes_written,
+            amount,
+        );
+    }
+
+    fn count_flow_file_out(&self, relationship: &str) {
+        if !self.enabled.contains(StandardMetrics::FLOW_FILES_OUT) {
+            return;
+        }
+        let mut flow_files_out = self.counts.flow_files_out.borrow_mut();
+        match flow_files_out
+            .iter_mut()
+            .find(|(name, _)| name == relationship)
+        {
+            Some((_, count)) => *count += 1,
+            None => flow_files_out.push((relationship.to_string(), 1)),
+        }
+    }
+}
+
+impl<'s, PS: ProcessSession> ProcessSession for MeteredSession<'s, PS> {
+    type FlowFile = PS::FlowFile;
+
+    fn create(&mut self) -> Result<Self::FlowFile, MinifiError> {
+        self.session.create()
+    }
+
+    fn create_child(&mut self, parent: &Self::FlowFile) -> Result<Self::FlowFile, MinifiError> {
+        self.session.create_child(parent)
+    }
+
+    fn clone_flow_file(
+        &mut self,
+        flow_file: &Self::FlowFile,
+    ) -> Result<Self::FlowFile, MinifiError> {
+        self.session.clone_flow_file(flow_file)
+    }
+
+    fn get(&mut self) -> Option<Self::FlowFile> {
+        let flow_file = self.session.get();
+        if flow_file.is_some() {
+            self.count_flow_file_in();
+        }
+        flow_file
+    }
+
+    fn transfer(&self, flow_file: Self::FlowFile, relationship: &str) -> Result<(), MinifiError> {
+        self.session.transfer(flow_file, relationship)?;
+        self.count_flow_file_out(relationship);
+        Ok(())
+    }
+
+    fn remove(&mut self, flow_file: Self::FlowFile) -> Result<(), MinifiError> {
+        self.session.remove(flow_file)
+    }
+
+    fn set_attribute(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        attr_key: &str,
+        attr_value: &str,
+    ) -> Result<(), MinifiError> {
+        self.session.set_attribute(flow_file, attr_key, attr_value)
+    }
+
+    fn remove_attribute(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        attr_key: &str,
+    ) -> Result<(), MinifiError> {
+        self.session.remove_attribute(flow_file, attr_key)
+    }
+
+    fn get_attribute(&self, flow_file: &Self::FlowFile, attr_key: &str) -> Option<String> {
+        self.session.get_attribute(flow_file, attr_key)
+    }
+
+    fn on_attributes<F: FnMut(&str, &str)>(
+        &self,
+        flow_file: &Self::FlowFile,
+        process_attr: F,
+    ) -> bool {
+        self.session.on_attributes(flow_file, process_attr)
+    }
+
+    fn write(&self, flow_file: &Self::FlowFile, data: &[u8]) -> Result<(), MinifiError> {
+        self.session.write(flow_file, data)?;
+        self.count_bytes_written(data.len() as u64);
+        Ok(())
+    }
+
+    fn write_lazy<'a>(
+        &self,
+        flow_file: &Self::FlowFile,
+        stream: Box<dyn Read + 'a>,
+    ) -> Result<(), MinifiError> {
+        let written = Cell::new(0);
+        self.session.write_lazy(
+            flow_file,
+            Box::new(CountingReader {
+                inner: stream,
+                count: &written,
+            }),
+        )?;
+        self.count_bytes_written(written.get());
+        Ok(())
+    }
+
+    fn write_vectored(
+        &self,
+        flow_file: &Self::FlowFile,
+        slices: &[&[u8]],
+        chunk_size: usize,
+    ) -> Result<(), MinifiError> {
+        self.session.write_vectored(flow_file, slices, chunk_size)?;
+        self.count_bytes_written(slices.iter().map(|slice| slice.len() as u64).sum());
+        Ok(())
+    }
+
+    fn write_stream<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
+    where
+        F: FnOnce(&mut dyn OutputStream) -> Result<(R, IoState), MinifiError>,
+    {
+        let mut written = 0;
+        let result = self.session.write_stream(flow_file, |output_stream| {
+            let mut counting_stream = CountingWriter {
+                inner: output_stream,
+                count: 0,
+            };
+            let (result, io_state) = callback(&mut counting_stream)?;
+            if io_state == IoState::Ok {
+                written = counting_stream.count;
+            }
+            Ok((result, io_state))
+        })?;
+        self.count_bytes_written(written);
+        Ok(result)
+    }
+
+    // only the appended bytes count as written, not the copy of the old content
+    fn append<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
+    where
+        F: FnOnce(&mut dyn OutputStream) -> Result<(R, IoState), MinifiError>,
+    {
+        let mut written = 0;
+        let result = self.session.append(flow_file, |output_stream| {
+            let mut counting_stream = CountingWriter {
+                inner: output_stream,
+                count: 0,
+            };
+            let (result, io_state) = callback(&mut counting_stream)?;
+            if io_state == IoState::Ok {
+                written = counting_stream.count;
+            }
+            Ok((result, io_state))
+        })?;
+        self.count_bytes_written(written);
+        Ok(result)
+    }
+
+    fn read(&self, flow_file: &Self::FlowFile) -> Option<Vec<u8>> {
+        let content = self.session.read(flow_file)?;
+        self.count_bytes_read(content.len() as u64);
+        Some(content)
+    }
+
+    fn read_stream<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
+    where
+        F: FnOnce(&mut dyn InputStream) -> Result<R, MinifiError>,
+    {
+        let mut read = 0;
+        let result = self.session.read_stream(flow_file, |input_stream| {
+            let mut counting_stream = CountingBufReader {
+                inner: input_stream,
+                count: 0,
+            };
+            let result = callback(&mut counting_stream);
+            read = counting_stream.count;
+            result
+        })?;
+        self.count_bytes_read(read);
+        Ok(result)
+    }
+
+    fn read_in_batches<F>(
+        &self,
+        flow_file: &Self::FlowFile,
+        batch_size: usize,
+        mut process_batch: F,
+    ) -> Result<(), MinifiError>
+    where
+        F: FnMut(&[u8]) -> Result<(), MinifiError>,
+    {
+        let mut read = 0;
+        self.session
+            .read_in_batches(flow_file, batch_size, |batch| {
+                read += batch.len() as u64;
+                process_batch(batch)
+            })?;
+        self.count_bytes_read(read);
+        Ok(())
+    }
+}
+
+struct CountingReader<'a, 'c> {
+    inner: Box<dyn Read + 'a>,
+    count: &'c Cell<u64>,
+}
+
+impl Read for CountingReader<'_, '_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.set(self.count.get() + read as u64);
+        Ok(read)
+    }
+}
+
+#[derive(Debug)]
+struct CountingWriter<'a> {
+    inner: &'a mut dyn OutputStream,
+    count: u64,
+}
+
+impl Write for CountingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[derive(Debug)]
+struct CountingBufReader<'a> {
+    inner: &'a mut dyn InputStream,
+    count: u64,
+}
+
+impl Read for CountingBufReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
+
+impl BufRead for CountingBufReader<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount);
+        self.count += amount as u64;
+    }
+}
+
+impl Seek for CountingBufReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner
+            .as_seekable()
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::Unsupported))?
+            .seek(pos)
+    }
+}
+
+impl InputStream for CountingBufReader<'_> {
+    fn size(&self) -> Option<u64> {
+        self.inner.size()
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        if self.inner.as_seekable().is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockFlowFile, MockProcessSession};
+
+    fn metric(metrics: &[(String, f64)], name: &str) -> Option<f64> {
+        metrics
+            .iter()
+            .find(|(metric_name, _)| metric_name == name)
+            .map(|(_, value)| *value)
+    }
+
+    fn copy_to_success(
+        session: &mut MeteredSession<'_, MockProcessSession>,
+    ) -> Result<OnTriggerResult, MinifiError> {
+        let Some(flow_file) = session.get() else {
+            return Ok(OnTriggerResult::Yield);
+        };
+        let content = session.read(&flow_file).unwrap_or_default();
+        let copy = session.create()?;
+        session.write(&copy, &content)?;
+        session.transfer(copy, "success")?;
+        session.transfer(flow_file, "original")?;
+        Ok(OnTriggerResult::Ok)
+    }
+
+    #[test]
+    fn counts_triggers_flow_files_and_bytes() {
+        let metrics = ProcessorMetrics::new(StandardMetrics::ALL);
+        let mut session = MockProcessSession::new();
+        session
+            .input_flow_files
+            .push(MockFlowFile::with_content(b"hello"));
+
+        measured_on_trigger(&metrics, &mut session, copy_to_success).unwrap();
+        measured_on_trigger(&metrics, &mut session, copy_to_success).unwrap();
+        let _ = measured_on_trigger(&metrics, &mut session, |_| {
+            Err(MinifiError::trigger_err("boom"))
+        });
+
+        let published = metrics.calculate_metrics();
+        assert_eq!(metric(&published, "OnTriggerInvocations"), Some(3.0));
+        assert_eq!(metric(&published, "YieldedTriggers"), Some(1.0));
+        assert_eq!(metric(&published, "FailedTriggers"), Some(1.0));
+        assert_eq!(metric(&published, "IncomingFlowFiles"), Some(1.0));
+        assert_eq!(metric(&published, "BytesRead"), Some(5.0));
+        assert_eq!(metric(&published, "BytesWritten"), Some(5.0));
+        assert_eq!(
+            metric(&published, "TransferredFlowFiles.success"),
+            Some(1.0)
+        );
+        assert_eq!(
+            metric(&published, "TransferredFlowFiles.original"),
+            Some(1.0)
+        );
+        assert!(metric(&published, "OnTriggerLatencyP99Ms").is_some());
+    }
+
+    #[test]
+    fn failed_trigger_is_not_counted_as_throughput() {
+        let metrics = ProcessorMetrics::new(StandardMetrics::ALL);
+        let mut session = MockProcessSession::new();
+        session.input_flow_files.push(MockFlowFile::new());
+
+        let _ = measured_on_trigger(&metrics, &mut session, |session| {
+            let flow_file = session.get().unwrap();
+            session.transfer(flow_file, "success")?;
+            Err(MinifiError::trigger_err("boom"))
+        });
+
+        let published = metrics.calculate_metrics();
+        assert_eq!(metric(&published, "IncomingFlowFiles"), Some(0.0));
+        assert_eq!(metric(&published, "TransferredFlowFiles.success"), None);
+    }
+
+    #[test]
+    fn disabled_metrics_are_not_published() {
+        let metrics = ProcessorMetrics::new(
+            StandardMetrics::ALL
+                .without(StandardMetrics::LATENCY)
+                .without(StandardMetrics::FLOW_FILES_OUT),
+        );
+        let mut session = MockProcessSession::new();
+        session.input_flow_files.push(MockFlowFile::new());
+        measured_on_trigger(&metrics, &mut session, copy_to_success).unwrap();
+
+        let published = metrics.calculate_metrics();
+        assert_eq!(metric(&published, "OnTriggerInvocations"), Some(1.0));
+        assert!(metric(&published, "OnTriggerLatencyP50Ms").is_none());
+        assert!(metric(&published, "TransferredFlowFiles.success").is_none());
+
+        let metrics = ProcessorMetrics::new(StandardMetrics::NONE);
+        assert!(metrics.calculate_metrics().is_empty());
+    }
+
+    #[test]
+    fn relationships_beyond_the_slots_are_counted_together() {
+        let transfer_counters = TransferCounters::new();
+        for slot in 0..RELATIONSHIP_SLOTS + 2 {
+            transfer_counters.add(&format!("relationship_{slot}"), 1);
+        }
+        transfer_counters.add("relationship_0", 2);
+        transfer_counters.add("relationship_33", 1);
+
+        let mut published = Vec::new();
+        transfer_counters.publish(&mut published);
+        assert_eq!(published.len(), RELATIONSHIP_SLOTS + 1);
+        assert_eq!(
+            metric(&published, "TransferredFlowFiles.relationship_0"),
+            Some(3.0)
+        );
+        assert_eq!(
+            metric(&published, "TransferredFlowFilesToOtherRelationships"),
+            Some(3.0)
+        );
+    }
+
+    #[test]
+    fn latency_percentiles() {
+        let metrics = ProcessorMetrics::new(StandardMetrics::LATENCY);
+        let published = metrics.calculate_metrics();
+        assert_eq!(metric(&published, "OnTriggerLatencyP50Ms"), Some(0.0));
+
+        for millis in 1..=100 {
+            metrics.record_trigger(
+                &Ok(OnTriggerResult::Ok),
+                TriggerCounts::default(),
+                Duration::from_millis(millis),
+            );
+        }
+        // the upper bounds of the power of two microsecond buckets
+        let published = metrics.calculate_metrics();
+        assert_eq!(metric(&published, "OnTriggerLatencyP50Ms"), Some(65.535));
+        assert_eq!(metric(&published, "OnTriggerLatencyP99Ms"), Some(131.071));
+    }
+}
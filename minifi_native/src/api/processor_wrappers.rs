@@ -1,5 +1,7 @@
 pub(crate) mod complex_processor;
+pub(crate) mod flow_file_aggregator;
 pub(crate) mod flow_file_source;
+pub(crate) mod flow_file_split_transform;
 pub(crate) mod flow_file_stream_transform;
 pub(crate) mod flow_file_transform;
 pub(crate) mod utils;
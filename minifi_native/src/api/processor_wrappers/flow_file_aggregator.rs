@@ -0,0 +1,737 @@
+use crate::api::processor::{AdvancedProcessorFeatures, Processor};
+use crate::api::processor_wrappers::utils::context_session_flowfile_bundle::ContextSessionFlowFileBundle;
+use crate::api::property::{GetControllerService, GetProperty};
+use crate::api::raw_processor::SingleThreadedTrigger;
+use crate::{
+    CalculateMetrics, DynamicRelationship, Exclusive, FlowFile, GetAttribute, LogLevel, Logger,
+    MinifiError, OnTriggerResult, ProcessContext, ProcessSession, Relationship, Schedule,
+};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// When the bins of a FlowFileAggregator are ready to be merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinLimits {
+    min_entries: usize,
+    max_entries: usize,
+    max_bin_size: Option<u64>,
+    max_bin_age: Option<Duration>,
+}
+
+impl BinLimits {
+    /// A bin is merged as soon as it has max_entries entries
+    pub const fn new(min_entries: usize, max_entries: usize) -> Self {
+        Self {
+            min_entries,
+            max_entries,
+            max_bin_size: None,
+            max_bin_age: None,
+        }
+    }
+
+    /// A bin is merged once its content reaches max_bin_size bytes, entries that would overflow it start a new bin
+    pub const fn with_max_bin_size(self, max_bin_size: u64) -> Self {
+        Self {
+            max_bin_size: Some(max_bin_size),
+            ..self
+        }
+    }
+
+    /// A bin with at least min_entries entries is merged once it is older than max_bin_age
+    pub const fn with_max_bin_age(self, max_bin_age: Duration) -> Self {
+        Self {
+            max_bin_age: Some(max_bin_age),
+            ..self
+        }
+    }
+
+    pub fn min_entries(&self) -> usize {
+        self.min_entries
+    }
+
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    pub fn max_bin_size(&self) -> Option<u64> {
+        self.max_bin_size
+    }
+
+    pub fn max_bin_age(&self) -> Option<Duration> {
+        self.max_bin_age
+    }
+}
+
+/// Content and attributes of a binned flow file, read when its bin is merged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinEntry {
+    content: Vec<u8>,
+    attributes: HashMap<String, String>,
+}
+
+impl BinEntry {
+    pub fn new(content: Vec<u8>, attributes: HashMap<String, String>) -> Self {
+        Self {
+            content,
+            attributes,
+        }
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+}
+
+/// A flow file of the current trigger, with the time its bin was started
+#[derive(Debug)]
+struct BinnedFlowFile<FF> {
+    flow_file: FF,
+    size: u64,
+    // milliseconds since the Unix epoch, read from BIN_ENTRY_TIME_ATTRIBUTE if it was binned before
+    entry_time: u64,
+    has_entry_time: bool,
+}
+
+#[derive(Debug)]
+struct Bin<FF> {
+    flow_files: Vec<BinnedFlowFile<FF>>,
+    size: u64,
+}
+
+impl<FF> Bin<FF> {
+    fn new() -> Self {
+        Self {
+            flow_files: Vec::new(),
+            size: 0,
+        }
+    }
+
+    fn is_full(&self, limits: &BinLimits) -> bool {
+        self.flow_files.len() >= limits.max_entries
+            || limits
+                .max_bin_size
+                .is_some_and(|max_bin_size| self.size >= max_bin_size)
+    }
+
+    // the flow files are sorted by entry time, so the first one is the oldest
+    fn is_expired(&self, limits: &BinLimits, now: u64) -> bool {
+        self.flow_files.len() >= limits.min_entries
+            && limits.max_bin_age.is_some_and(|max_bin_age| {
+                self.flow_files.first().is_some_and(|oldest| {
+                    u128::from(now.saturating_sub(oldest.entry_time)) >= max_bin_age.as_millis()
+                })
+            })
+    }
+}
+
+/// Fills the bins of a correlation key in the order the flow files entered them.
+/// Returns the bins that are ready to be merged and the last bin, if it isn't full yet.
+fn fill_bins<FF>(
+    mut flow_files: Vec<BinnedFlowFile<FF>>,
+    limits: &BinLimits,
+) -> (Vec<Bin<FF>>, Option<Bin<FF>>) {
+    flow_files.sort_by_key(|flow_file| flow_file.entry_time);
+    let mut ready_bins = Vec::new();
+    let mut bin = Bin::new();
+    for flow_file in flow_files {
+        if !bin.flow_files.is_empty()
+            && let Some(max_bin_size) = limits.max_bin_size
+            && bin.size + flow_file.size > max_bin_size
+        {
+            ready_bins.push(std::mem::replace(&mut bin, Bin::new()));
+        }
+        bin.size += flow_file.size;
+        bin.flow_files.push(flow_file);
+        if bin.is_full(limits) {
+            ready_bins.push(std::mem::replace(&mut bin, Bin::new()));
+        }
+    }
+    let open_bin = (!bin.flow_files.is_empty()).then_some(bin);
+    (ready_bins, open_bin)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_millis() as u64)
+}
+
+pub struct MergedFlowFile {
+    target_relationship: DynamicRelationship,
+    content: Vec<u8>,
+    attributes_to_add: HashMap<String, String>,
+}
+
+impl MergedFlowFile {
+    pub fn new<R: Into<DynamicRelationship>>(
+        target_relationship: R,
+        content: Vec<u8>,
+        attributes_to_add: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            target_relationship: target_relationship.into(),
+            content,
+            attributes_to_add,
+        }
+    }
+
+    pub fn target_relationship_name(&self) -> &str {
+        self.target_relationship.name()
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    pub fn attributes_to_add(&self) -> &HashMap<String, String> {
+        &self.attributes_to_add
+    }
+}
+
+/// Attribute of the binned flow files, the time their bin was started in milliseconds since the Unix epoch
+pub const BIN_ENTRY_TIME_ATTRIBUTE: &str = "bin.entry.time";
+
+/// Many-to-one processor kind, the incoming flow files are collected into bins by correlation key
+/// and each ready bin is merged into a single flow file.
+///
+/// The C API can't take flow files out of a session, so the bins are held the same way the agent's own binning processors do it:
+/// the flow files of a bin that isn't ready yet are routed to BINNED_RELATIONSHIP,
+/// which has to be connected back to the processor, and are binned again on the next trigger.
+/// The bin age is kept in BIN_ENTRY_TIME_ATTRIBUTE, which is removed once the bin is merged.
+///
+/// Only available with the Exclusive threading model, so every trigger sees all the binned flow files.
+pub trait FlowFileAggregator {
+    /// The binned flow files are routed here once their bin is merged
+    const ORIGINAL_RELATIONSHIP: Relationship;
+    /// The flow files of the bins that aren't ready yet are routed here, it has to loop back to the processor
+    const BINNED_RELATIONSHIP: Relationship;
+
+    fn bin_limits(&self) -> &BinLimits;
+
+    fn correlation_key<Ctx: GetProperty + GetControllerService + GetAttribute>(
+        &self,
+        context: &Ctx,
+    ) -> Result<String, MinifiError>;
+
+    fn merge<LoggerImpl: Logger>(
+        &mut self,
+        correlation_key: &str,
+        entries: &[BinEntry],
+        logger: &LoggerImpl,
+    ) -> Result<MergedFlowFile, MinifiError>;
+}
+
+pub struct FlowFileAggregatorProcessorType {}
+
+/// The C API creates flow files with a single parent,
+/// so the merged flow file is only the child of the oldest flow file in the bin, not of all of them
+fn write_merged<Implementation, PS, L>(
+    implementation: &mut Implementation,
+    session: &mut PS,
+    correlation_key: &str,
+    flow_files: &[PS::FlowFile],
+    logger: &L,
+) -> Result<(), MinifiError>
+where
+    Implementation: FlowFileAggregator,
+    PS: ProcessSession,
+    L: Logger,
+{
+    let entries = flow_files
+        .iter()
+        .map(|flow_file| {
+            let content = session.read_stream(flow_file, |input_stream| {
+                let mut content = Vec::new();
+                input_stream.read_to_end(&mut content)?;
+                Ok(content)
+            })?;
+            let mut attributes = session.get_attributes(flow_file);
+            attributes.remove(BIN_ENTRY_TIME_ATTRIBUTE);
+            Ok(BinEntry::new(content, attributes))
+        })
+        .collect::<Result<Vec<_>, MinifiError>>()?;
+    let merged = implementation.merge(correlation_key, &entries, logger)?;
+
+    let mut merged_flow_file = session.create_child(&flow_files[0])?;
+    session.remove_attribute(&mut merged_flow_file, BIN_ENTRY_TIME_ATTRIBUTE)?;
+    session.write(&merged_flow_file, &merged.content)?;
+    for (k, v) in &merged.attributes_to_add {
+        session.set_attribute(&mut merged_flow_file, k, v)?;
+    }
+    session.transfer(merged_flow_file, merged.target_relationship.name())
+}
+
+/// Merges the bin, its flow files go to the original relationship in the same session as the merged one
+fn emit_merged<Implementation, PS, L>(
+    implementation: &mut Implementation,
+    session: &mut PS,
+    correlation_key: &str,
+    bin: Bin<PS::FlowFile>,
+    logger: &L,
+) -> Result<(), MinifiError>
+where
+    Implementation: FlowFileAggregator,
+    PS: ProcessSession,
+    L: Logger,
+{
+    let flow_files: Vec<_> = bin
+        .flow_files
+        .into_iter()
+        .map(|binned_flow_file| binned_flow_file.flow_file)
+        .collect();
+    write_merged(
+        implementation,
+        session,
+        correlation_key,
+        &flow_files,
+        logger,
+    )?;
+    for mut flow_file in flow_files {
+        session.remove_attribute(&mut flow_file, BIN_ENTRY_TIME_ATTRIBUTE)?;
+        session.transfer(flow_file, Implementation::ORIGINAL_RELATIONSHIP.name)?;
+    }
+    Ok(())
+}
+
+/// Sends the flow files of a bin that isn't ready yet around the loop, marked with the time the bin was started
+fn hold_bin<Implementation, PS>(session: &mut PS, bin: Bin<PS::FlowFile>) -> Result<(), MinifiError>
+where
+    Implementation: FlowFileAggregator,
+    PS: ProcessSession,
+{
+    for binned_flow_file in bin.flow_files {
+        let mut flow_file = binned_flow_file.flow_file;
+        if !binned_flow_file.has_entry_time {
+            session.set_attribute(
+                &mut flow_file,
+                BIN_ENTRY_TIME_ATTRIBUTE,
+                &binned_flow_file.entry_time.to_string(),
+            )?;
+        }
+        session.transfer(flow_file, Implementation::BINNED_RELATIONSHIP.name)?;
+    }
+    Ok(())
+}
+
+fn bin_flow_file<Implementation, PC, PS>(
+    implementation: &Implementation,
+    context: &mut PC,
+    session: &mut PS,
+    flow_file: PS::FlowFile,
+    now: u64,
+) -> Result<(String, BinnedFlowFile<PS::FlowFile>), MinifiError>
+where
+    Implementation: FlowFileAggregator,
+    PC: ProcessContext,
+    PS: ProcessSession<FlowFile = PC::FlowFile>,
+{
+    let key = {
+        let simple_context = ContextSessionFlowFileBundle::new(context, session, Some(&flow_file));
+        implementation.correlation_key(&simple_context)?
+    };
+    let entry_time = session
+        .get_attribute(&flow_file, BIN_ENTRY_TIME_ATTRIBUTE)
+        .and_then(|entry_time| entry_time.parse::<u64>().ok());
+    Ok((
+        key,
+        BinnedFlowFile {
+            size: flow_file.size()?,
+            flow_file,
+            entry_time: entry_time.unwrap_or(now),
+            has_entry_time: entry_time.is_some(),
+        },
+    ))
+}
+
+fn handle_aggregation<Implementation, PC, PS, L>(
+    implementation: &mut Implementation,
+    context: &mut PC,
+    session: &mut PS,
+    logger: &L,
+) -> Result<OnTriggerResult, MinifiError>
+where
+    Implementation: FlowFileAggregator,
+    PC: ProcessContext,
+    PS: ProcessSession<FlowFile = PC::FlowFile>,
+    L: Logger,
+{
+    let now = now_millis();
+    let mut received = 0;
+    // correlation keys in the order they were first seen, for a deterministic merge order
+    let mut keys = Vec::new();
+    let mut flow_files_by_key: HashMap<String, Vec<BinnedFlowFile<PS::FlowFile>>> = HashMap::new();
+    while let Some(flow_file) = session.get() {
+        received += 1;
+        let (key, binned_flow_file) =
+            bin_flow_file(implementation, context, session, flow_file, now)?;
+        match flow_files_by_key.entry(key) {
+            Entry::Occupied(mut entry) => entry.get_mut().push(binned_flow_file),
+            Entry::Vacant(entry) => {
+                keys.push(entry.key().clone());
+                entry.insert(vec![binned_flow_file]);
+            }
+        }
+    }
+
+    let mut merged = 0;
+    for key in keys {
+        let Some(flow_files) = flow_files_by_key.remove(&key) else {
+            continue;
+        };
+        let limits = *implementation.bin_limits();
+        let (ready_bins, open_bin) = fill_bins(flow_files, &limits);
+        for bin in ready_bins {
+            emit_merged(implementation, session, &key, bin, logger)?;
+            merged += 1;
+        }
+        match open_bin {
+            Some(bin) if bin.is_expired(&limits, now) => {
+                emit_merged(implementation, session, &key, bin, logger)?;
+                merged += 1;
+            }
+            Some(bin) => hold_bin::<Implementation, PS>(session, bin)?,
+            None => {}
+        }
+    }
+
+    logger.log(
+        LogLevel::Trace,
+        format_args!("Binned {received} flow files, merged {merged} bins"),
+    );
+    // the held flow files are back in the incoming queue right away, yield instead of spinning on them
+    if merged == 0 {
+        Ok(OnTriggerResult::Yield)
+    } else {
+        Ok(OnTriggerResult::Ok)
+    }
+}
+
+impl<Implementation, L> SingleThreadedTrigger
+    for Processor<Implementation, FlowFileAggregatorProcessorType, Exclusive, L>
+where
+    Implementation: Schedule + CalculateMetrics + FlowFileAggregator + AdvancedProcessorFeatures,
+    L: Logger,
+{
+    fn on_trigger<PC, PS>(
+        &mut self,
+        context: &mut PC,
+        session: &mut PS,
+    ) -> Result<OnTriggerResult, MinifiError>
+    where
+        PC: ProcessContext,
+        PS: ProcessSession<FlowFile = PC::FlowFile>,
+    {
+        if let Some(ref mut scheduled_impl) = self.scheduled_impl {
+            handle_aggregation(scheduled_impl, context, session, &self.logger)
+        } else {
+            Err(MinifiError::trigger_err(
+                "The processor hasn't been scheduled yet",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::RawProcessor;
+    use crate::{
+        InstanceMetadata, MockFlowFile, MockLogger, MockProcessContext, MockProcessSession,
+    };
+
+    const MERGED: Relationship = Relationship {
+        name: "merged",
+        description: "",
+    };
+
+    const ORIGINAL: Relationship = Relationship {
+        name: "original",
+        description: "",
+    };
+
+    const BINNED: Relationship = Relationship {
+        name: "binned",
+        description: "",
+    };
+
+    struct Concatenate {
+        limits: BinLimits,
+        fail_merge: bool,
+    }
+
+    impl Schedule for Concatenate {
+        fn schedule<Ctx: GetProperty, L: Logger>(
+            _context: &Ctx,
+            _logger: &L,
+        ) -> Result<Self, MinifiError> {
+            Ok(Self {
+                limits: BinLimits::new(2, 2),
+                fail_merge: false,
+            })
+        }
+    }
+
+    impl CalculateMetrics for Concatenate {}
+
+    impl AdvancedProcessorFeatures for Concatenate {
+        fn get_trigger_when_empty(&self) -> bool {
+            false
+        }
+        fn is_work_available(&self) -> bool {
+            false
+        }
+    }
+
+    impl FlowFileAggregator for Concatenate {
+        const ORIGINAL_RELATIONSHIP: Relationship = ORIGINAL;
+        const BINNED_RELATIONSHIP: Relationship = BINNED;
+
+        fn bin_limits(&self) -> &BinLimits {
+            &self.limits
+        }
+
+        fn correlation_key<Ctx: GetProperty + GetControllerService + GetAttribute>(
+            &self,
+            context: &Ctx,
+        ) -> Result<String, MinifiError> {
+            Ok(context.get_attribute("group")?.unwrap_or_default())
+        }
+
+        fn merge<LoggerImpl: Logger>(
+            &mut self,
+            correlation_key: &str,
+            entries: &[BinEntry],
+            _logger: &LoggerImpl,
+        ) -> Result<MergedFlowFile, MinifiError> {
+            if self.fail_merge {
+                return Err(MinifiError::trigger_err("merge failed"));
+            }
+            Ok(MergedFlowFile::new(
+                &MERGED,
+                entries.iter().flat_map(|e| e.content().to_vec()).collect(),
+                HashMap::from([("group".to_string(), correlation_key.to_string())]),
+            ))
+        }
+    }
+
+    type ConcatenateProcessor =
+        Processor<Concatenate, FlowFileAggregatorProcessorType, Exclusive, MockLogger>;
+
+    fn flow_file(group: &str, content: &str) -> MockFlowFile {
+        let mut flow_file = MockFlowFile::with_content(content.as_bytes());
+        flow_file
+            .attributes
+            .insert("group".to_string(), group.to_string());
+        flow_file
+    }
+
+    fn binned(size: usize, entry_time: u64) -> BinnedFlowFile<usize> {
+        BinnedFlowFile {
+            flow_file: size,
+            size: size as u64,
+            entry_time,
+            has_entry_time: false,
+        }
+    }
+
+    fn bin_contents(bin: &Bin<usize>) -> Vec<usize> {
+        bin.flow_files.iter().map(|ff| ff.flow_file).collect()
+    }
+
+    fn scheduled_processor(limits: BinLimits) -> ConcatenateProcessor {
+        let mut processor =
+            ConcatenateProcessor::new(InstanceMetadata::default(), MockLogger::new());
+        processor.on_schedule(&MockProcessContext::new()).unwrap();
+        processor.scheduled_impl.as_mut().unwrap().limits = limits;
+        processor
+    }
+
+    /// The next session gets the flow files the previous one routed to the self-loop, like the agent's connection would do
+    fn looped_back(session: &MockProcessSession) -> MockProcessSession {
+        let mut next_session = MockProcessSession::new();
+        next_session.input_flow_files = session
+            .transferred_flow_files
+            .borrow_mut()
+            .extract_if(.., |ff| ff.relationship == BINNED.name)
+            .map(|ff| ff.flow_file)
+            .collect();
+        next_session
+    }
+
+    fn merged_contents(session: &MockProcessSession) -> Vec<String> {
+        session
+            .transferred_flow_files
+            .borrow()
+            .iter()
+            .filter(|ff| ff.relationship == MERGED.name)
+            .map(|ff| String::from_utf8(ff.flow_file.content.borrow().clone()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn bin_is_ready_at_max_entries() {
+        let (ready, open) = fill_bins(
+            vec![binned(1, 0), binned(2, 0), binned(3, 0)],
+            &BinLimits::new(1, 2),
+        );
+        assert_eq!(ready.len(), 1);
+        assert_eq!(bin_contents(&ready[0]), vec![1, 2]);
+        assert_eq!(bin_contents(&open.unwrap()), vec![3]);
+    }
+
+    #[test]
+    fn bins_are_filled_in_entry_order() {
+        let (ready, open) = fill_bins(
+            vec![binned(1, 20), binned(2, 10), binned(3, 10)],
+            &BinLimits::new(1, 2),
+        );
+        assert_eq!(bin_contents(&ready[0]), vec![2, 3]);
+        assert_eq!(bin_contents(&open.unwrap()), vec![1]);
+    }
+
+    #[test]
+    fn overflowing_entry_starts_a_new_bin() {
+        let limits = BinLimits::new(1, 10).with_max_bin_size(10);
+        let (ready, open) = fill_bins(vec![binned(6, 0), binned(6, 0)], &limits);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].size, 6);
+        assert_eq!(open.as_ref().unwrap().size, 6);
+
+        let (ready, open) = fill_bins(vec![binned(6, 0), binned(4, 0)], &limits);
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].size, 10);
+        assert!(open.is_none());
+    }
+
+    #[test]
+    fn expired_bins_need_min_entries() {
+        let limits = BinLimits::new(2, 10).with_max_bin_age(Duration::from_millis(100));
+        let (_, open) = fill_bins(vec![binned(1, 1000)], &limits);
+        assert!(!open.unwrap().is_expired(&limits, 2000));
+
+        let (_, open) = fill_bins(vec![binned(1, 1000), binned(1, 1950)], &limits);
+        let open = open.unwrap();
+        assert!(!open.is_expired(&limits, 1099));
+        assert!(open.is_expired(&limits, 1100));
+    }
+
+    #[test]
+    fn holds_bins_on_the_self_loop_until_they_are_ready() {
+        let mut context = MockProcessContext::new();
+        let mut processor = scheduled_processor(BinLimits::new(2, 2));
+
+        let mut session = MockProcessSession::new();
+        session.input_flow_files.push(flow_file("x", "1"));
+        session.input_flow_files.push(flow_file("y", "2"));
+        assert_eq!(
+            processor.on_trigger(&mut context, &mut session).unwrap(),
+            OnTriggerResult::Yield
+        );
+        assert_eq!(session.num_of_flow_files_transferred_to(BINNED.name), 2);
+        assert!(session.transferred_flow_files.borrow().iter().all(|ff| {
+            ff.flow_file
+                .attributes
+                .contains_key(BIN_ENTRY_TIME_ATTRIBUTE)
+        }));
+
+        let mut session = looped_back(&session);
+        session.input_flow_files.insert(0, flow_file("y", "3"));
+        assert_eq!(
+            processor.on_trigger(&mut context, &mut session).unwrap(),
+            OnTriggerResult::Ok
+        );
+        assert_eq!(merged_contents(&session), vec!["23"]);
+        assert_eq!(session.num_of_flow_files_transferred_to(ORIGINAL.name), 2);
+        assert_eq!(session.num_of_flow_files_transferred_to(BINNED.name), 1);
+        let transferred = session.transferred_flow_files.borrow();
+        let merged = transferred
+            .iter()
+            .find(|ff| ff.relationship == MERGED.name)
+            .unwrap();
+        assert_eq!(
+            merged.flow_file.attributes.get("group"),
+            Some(&"y".to_string())
+        );
+        assert!(
+            transferred
+                .iter()
+                .filter(|ff| ff.relationship != BINNED.name)
+                .all(|ff| !ff
+                    .flow_file
+                    .attributes
+                    .contains_key(BIN_ENTRY_TIME_ATTRIBUTE))
+        );
+    }
+
+    #[test]
+    fn bin_age_is_kept_around_the_loop() {
+        let mut context = MockProcessContext::new();
+        let mut processor =
+            scheduled_processor(BinLimits::new(2, 10).with_max_bin_age(Duration::from_secs(60)));
+
+        let mut session = MockProcessSession::new();
+        session.input_flow_files.push(flow_file("x", "1"));
+        processor.on_trigger(&mut context, &mut session).unwrap();
+        let mut session = looped_back(&session);
+        let entry_time = session.input_flow_files[0].attributes[BIN_ENTRY_TIME_ATTRIBUTE].clone();
+
+        processor.on_trigger(&mut context, &mut session).unwrap();
+        let mut session = looped_back(&session);
+        assert_eq!(
+            session.input_flow_files[0].attributes[BIN_ENTRY_TIME_ATTRIBUTE],
+            entry_time
+        );
+
+        // a bin started long ago is merged once it reaches min entries
+        session.input_flow_files[0]
+            .attributes
+            .insert(BIN_ENTRY_TIME_ATTRIBUTE.to_string(), "0".to_string());
+        session.input_flow_files.push(flow_file("x", "2"));
+        processor.on_trigger(&mut context, &mut session).unwrap();
+        assert_eq!(merged_contents(&session), vec!["12"]);
+    }
+
+    // The C API creates flow files with a single parent, so the lineage only goes back to the oldest input
+    #[test]
+    fn merged_flow_file_is_a_child_of_its_oldest_input() {
+        let mut context = MockProcessContext::new();
+        let mut processor =
+            scheduled_processor(BinLimits::new(1, 10).with_max_bin_age(Duration::ZERO));
+
+        let mut session = MockProcessSession::new();
+        session.input_flow_files.push(flow_file("x", "2"));
+        session.input_flow_files.push(flow_file("x", "1"));
+        processor.on_trigger(&mut context, &mut session).unwrap();
+        assert_eq!(merged_contents(&session), vec!["12"]);
+        let transferred = session.transferred_flow_files.borrow();
+        assert!(
+            transferred[0]
+                .flow_file
+                .is_child_of(&transferred[1].flow_file)
+        );
+        assert!(transferred[1].flow_file.content_eq("1"));
+    }
+
+    #[test]
+    fn failed_merge_fails_the_trigger() {
+        let mut context = MockProcessContext::new();
+        let mut processor = scheduled_processor(BinLimits::new(2, 2));
+        processor.scheduled_impl.as_mut().unwrap().fail_merge = true;
+
+        let mut session = MockProcessSession::new();
+        session.input_flow_files.push(flow_file("x", "1"));
+        session.input_flow_files.push(flow_file("x", "2"));
+        assert!(processor.on_trigger(&mut context, &mut session).is_err());
+        assert_eq!(session.num_of_flow_files_transferred_to(MERGED.name), 0);
+        assert_eq!(session.num_of_flow_files_transferred_to(ORIGINAL.name), 0);
+    }
+}
@@ -2,33 +2,33 @@ use crate::api::processor::AdvancedProcessorFeatures;
 use crate::api::processor_wrappers::utils::flow_file_content::Content;
 use crate::api::raw_processor::{MultiThreadedTrigger, SingleThreadedTrigger};
 use crate::{
-    CalculateMetrics, Concurrent, Exclusive, GetControllerService, GetProperty, Logger,
-    MinifiError, OnTriggerResult, ProcessContext, ProcessSession, Processor, Relationship,
+    CalculateMetrics, Concurrent, DynamicRelationship, Exclusive, GetControllerService,
+    GetProperty, Logger, MinifiError, OnTriggerResult, ProcessContext, ProcessSession, Processor,
     Schedule,
 };
 use std::collections::HashMap;
 
 pub struct GeneratedFlowFile<'a> {
-    target_relationship_name: &'static str,
+    target_relationship: DynamicRelationship,
     new_content: Option<Content<'a>>,
     attributes_to_add: HashMap<String, String>,
 }
 
 impl<'a> GeneratedFlowFile<'a> {
-    pub fn new(
-        target_relationship: &'a Relationship,
+    pub fn new<R: Into<DynamicRelationship>>(
+        target_relationship: R,
         new_content: Option<Content<'a>>,
         attributes_to_add: HashMap<String, String>,
     ) -> Self {
         Self {
-            target_relationship_name: target_relationship.name,
+            target_relationship: target_relationship.into(),
             new_content,
             attributes_to_add,
         }
     }
 
-    pub fn target_relationship_name(&self) -> &'static str {
-        self.target_relationship_name
+    pub fn target_relationship_name(&self) -> &str {
+        self.target_relationship.name()
     }
 }
 
@@ -70,7 +70,7 @@ where
         for (k, v) in &new_flow_file_data.attributes_to_add {
             session.set_attribute(&mut ff, k, v)?;
         }
-        session.transfer(ff, new_flow_file_data.target_relationship_name)?;
+        session.transfer(ff, new_flow_file_data.target_relationship.name())?;
     }
     Ok(OnTriggerResult::Ok)
 }
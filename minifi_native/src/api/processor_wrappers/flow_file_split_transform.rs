@@ -0,0 +1,320 @@
+use crate::api::InputStream;
+use crate::api::processor::{AdvancedProcessorFeatures, Processor};
+use crate::api::processor_wrappers::utils::context_session_flowfile_bundle::ContextSessionFlowFileBundle;
+use crate::api::processor_wrappers::utils::transform_batch::{TransformBatch, process_batch};
+use crate::api::property::{GetControllerService, GetProperty};
+use crate::api::raw_processor::{MultiThreadedTrigger, SingleThreadedTrigger};
+use crate::{
+    CalculateMetrics, Concurrent, DynamicRelationship, Exclusive, GetAttribute, Logger,
+    MinifiError, OnTriggerResult, ProcessContext, ProcessSession, Schedule,
+};
+use std::collections::HashMap;
+
+/// One output of a FlowFileSplitTransform, created as a child of the input flow file
+pub struct SplitFlowFile {
+    target_relationship: DynamicRelationship,
+    content: Vec<u8>,
+    attributes_to_add: HashMap<String, String>,
+    attributes_to_remove: Vec<String>,
+}
+
+impl SplitFlowFile {
+    pub fn new<R: Into<DynamicRelationship>>(
+        target_relationship: R,
+        content: Vec<u8>,
+        attributes_to_add: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            target_relationship: target_relationship.into(),
+            content,
+            attributes_to_add,
+            attributes_to_remove: Vec::new(),
+        }
+    }
+
+    pub fn target_relationship_name(&self) -> &str {
+        self.target_relationship.name()
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    pub fn attributes_to_add(&self) -> &HashMap<String, String> {
+        &self.attributes_to_add
+    }
+
+    /// Attributes removed from the split, e.g. the ones inherited from the input flow file, before attributes_to_add are set
+    pub fn with_attributes_to_remove<I, S>(mut self, attributes_to_remove: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.attributes_to_remove = attributes_to_remove.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn attributes_to_remove(&self) -> &[String] {
+        &self.attributes_to_remove
+    }
+}
+
+pub struct SplitTransformResult {
+    splits: Vec<SplitFlowFile>,
+    original_relationship: Option<DynamicRelationship>,
+}
+
+impl SplitTransformResult {
+    /// The original flow file is removed after the splits are created
+    pub fn new(splits: Vec<SplitFlowFile>) -> Self {
+        Self {
+            splits,
+            original_relationship: None,
+        }
+    }
+
+    /// The original flow file is routed (unchanged) to the relationship after the splits are created
+    pub fn with_original<R: Into<DynamicRelationship>>(
+        splits: Vec<SplitFlowFile>,
+        original_relationship: R,
+    ) -> Self {
+        Self {
+            splits,
+            original_relationship: Some(original_relationship.into()),
+        }
+    }
+
+    pub fn splits(&self) -> &[SplitFlowFile] {
+        &self.splits
+    }
+
+    pub fn original_relationship_name(&self) -> Option<&str> {
+        self.original_relationship.as_ref().map(|r| r.name())
+    }
+}
+
+pub trait FlowFileSplitTransform {
+    /// How many flow files are transformed per on_trigger, override to drain the input in batches
+    const TRANSFORM_BATCH: TransformBatch = TransformBatch::SINGLE;
+
+    fn transform<Context: GetProperty + GetControllerService + GetAttribute, LoggerImpl: Logger>(
+        &self,
+        context: &Context,
+        input_stream: &mut dyn InputStream,
+        logger: &LoggerImpl,
+    ) -> Result<SplitTransformResult, MinifiError>;
+}
+
+pub trait MutFlowFileSplitTransform {
+    /// How many flow files are transformed per on_trigger, override to drain the input in batches
+    const TRANSFORM_BATCH: TransformBatch = TransformBatch::SINGLE;
+
+    fn transform<Context: GetProperty + GetControllerService + GetAttribute, LoggerImpl: Logger>(
+        &mut self,
+        context: &Context,
+        input_stream: &mut dyn InputStream,
+        logger: &LoggerImpl,
+    ) -> Result<SplitTransformResult, MinifiError>;
+}
+
+pub struct FlowFileSplitTransformProcessorType {}
+
+fn handle_split_transform<PC, PS, L, F>(
+    context: &mut PC,
+    session: &mut PS,
+    logger: &L,
+    batch: TransformBatch,
+    mut transform_fn: F,
+) -> Result<OnTriggerResult, MinifiError>
+where
+    PC: ProcessContext,
+    PS: ProcessSession<FlowFile = PC::FlowFile>,
+    L: Logger,
+    F: FnMut(
+        &ContextSessionFlowFileBundle<'_, PC, PS>,
+        &mut dyn InputStream,
+    ) -> Result<SplitTransformResult, MinifiError>,
+{
+    process_batch(session, batch, logger, |session, flow_file| {
+        let split_result = {
+            let simple_context =
+                ContextSessionFlowFileBundle::new(context, session, Some(flow_file));
+            session.read_stream(flow_file, |input_stream| {
+                transform_fn(&simple_context, input_stream)
+            })?
+        };
+
+        // the splits are only transferred once all of them are written,
+        // so a failing split doesn't leave the others behind next to a failed original
+        let mut children = Vec::with_capacity(split_result.splits.len());
+        if let Err(err) = create_splits(session, flow_file, split_result.splits, &mut children) {
+            for (child, _) in children {
+                session.remove(child)?;
+            }
+            return Err(err);
+        }
+        for (child, relationship) in children {
+            session.transfer(child, relationship.name())?;
+        }
+
+        Ok(split_result.original_relationship)
+    })
+}
+
+fn create_splits<PS: ProcessSession>(
+    session: &mut PS,
+    parent: &PS::FlowFile,
+    splits: Vec<SplitFlowFile>,
+    children: &mut Vec<(PS::FlowFile, DynamicRelationship)>,
+) -> Result<(), MinifiError> {
+    for split in splits {
+        let child = session.create_child(parent)?;
+        children.push((child, split.target_relationship));
+        let (child, _) = children
+            .last_mut()
+            .expect("the child was pushed right before");
+        session.write(child, &split.content)?;
+        for k in &split.attributes_to_remove {
+            session.remove_attribute(child, k)?;
+        }
+        session.set_attributes(child, &split.attributes_to_add)?;
+    }
+    Ok(())
+}
+
+impl<Implementation, L> MultiThreadedTrigger
+    for Processor<Implementation, FlowFileSplitTransformProcessorType, Concurrent, L>
+where
+    Implementation:
+        Schedule + CalculateMetrics + FlowFileSplitTransform + AdvancedProcessorFeatures,
+    L: Logger,
+{
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        Implementation::TRANSFORM_BATCH.failure_relationship_name();
+
+    fn on_trigger<PC, PS>(
+        &self,
+        context: &mut PC,
+        session: &mut PS,
+    ) -> Result<OnTriggerResult, MinifiError>
+    where
+        PC: ProcessContext,
+        PS: ProcessSession<FlowFile = PC::FlowFile>,
+    {
+        if let Some(ref scheduled_impl) = self.scheduled_impl {
+            handle_split_transform(
+                context,
+                session,
+                &self.logger,
+                Implementation::TRANSFORM_BATCH,
+                |ctx, input| scheduled_impl.transform(ctx, input, &self.logger),
+            )
+        } else {
+            Err(MinifiError::trigger_err(
+                "The processor hasn't been scheduled yet",
+            ))
+        }
+    }
+}
+
+impl<Implementation, L> SingleThreadedTrigger
+    for Processor<Implementation, FlowFileSplitTransformProcessorType, Exclusive, L>
+where
+    Implementation:
+        Schedule + CalculateMetrics + MutFlowFileSplitTransform + AdvancedProcessorFeatures,
+    L: Logger,
+{
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        Implementation::TRANSFORM_BATCH.failure_relationship_name();
+
+    fn on_trigger<PC, PS>(
+        &mut self,
+        context: &mut PC,
+        session: &mut PS,
+    ) -> Result<OnTriggerResult, MinifiError>
+    where
+        PC: ProcessContext,
+        PS: ProcessSession<FlowFile = PC::FlowFile>,
+    {
+        if let Some(ref mut scheduled_impl) = self.scheduled_impl {
+            handle_split_transform(
+                context,
+                session,
+                &self.logger,
+                Implementation::TRANSFORM_BATCH,
+                |ctx, input| scheduled_impl.transform(ctx, input, &self.logger),
+            )
+        } else {
+            Err(MinifiError::trigger_err(
+                "The processor hasn't been scheduled yet",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Relationship;
+    use crate::mock::test_processor::{scheduled, session_with, test_processor};
+    use std::io::BufRead;
+
+    const SPLITS: Relationship = Relationship {
+        name: "splits",
+        description: "",
+    };
+
+    const ORIGINAL: Relationship = Relationship {
+        name: "original",
+        description: "",
+    };
+
+    test_processor!(SplitLines);
+
+    impl FlowFileSplitTransform for SplitLines {
+        fn transform<
+            Context: GetProperty + GetControllerService + GetAttribute,
+            LoggerImpl: Logger,
+        >(
+            &self,
+            _context: &Context,
+            input_stream: &mut dyn InputStream,
+            _logger: &LoggerImpl,
+        ) -> Result<SplitTransformResult, MinifiError> {
+            let mut splits = Vec::new();
+            for (index, line) in input_stream.lines().enumerate() {
+                splits.push(
+                    SplitFlowFile::new(
+                        &SPLITS,
+                        line?.into_bytes(),
+                        HashMap::from([("line".to_string(), index.to_string())]),
+                    )
+                    .with_attributes_to_remove(["line.count"]),
+                );
+            }
+            Ok(SplitTransformResult::with_original(splits, &ORIGINAL))
+        }
+    }
+
+    #[test]
+    fn inherited_attributes_are_removed_from_the_splits() {
+        let (processor, mut context) = scheduled::<
+            Processor<SplitLines, FlowFileSplitTransformProcessorType, Concurrent, _>,
+        >();
+        let mut session = session_with(b"first\nsecond", &[("line.count", "2"), ("filename", "a")]);
+        processor.on_trigger(&mut context, &mut session).unwrap();
+
+        assert_eq!(session.num_of_flow_files_transferred_to(SPLITS.name), 2);
+        let transferred = session.transferred_flow_files.borrow();
+        let second = &transferred[1].flow_file;
+        assert_eq!(transferred[1].relationship, SPLITS.name);
+        assert!(second.content_eq("second"));
+        assert_eq!(second.attributes.get("line").unwrap(), "1");
+        assert!(!second.attributes.contains_key("line.count"));
+        assert_eq!(second.attributes.get("filename").unwrap(), "a");
+
+        let original = &transferred[2].flow_file;
+        assert_eq!(transferred[2].relationship, ORIGINAL.name);
+        assert_eq!(original.attributes.get("line.count").unwrap(), "2");
+    }
+}
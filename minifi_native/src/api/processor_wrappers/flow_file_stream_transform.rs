@@ -1,42 +1,46 @@
 use crate::api::process_session::IoState;
 use crate::api::processor::AdvancedProcessorFeatures;
 use crate::api::processor_wrappers::utils::context_session_flowfile_bundle::ContextSessionFlowFileBundle;
+use crate::api::processor_wrappers::utils::transform_batch::{TransformBatch, process_batch};
 use crate::api::raw_processor::{MultiThreadedTrigger, SingleThreadedTrigger};
 use crate::{
-    CalculateMetrics, Concurrent, Exclusive, GetAttribute, GetControllerService, GetProperty,
-    InputStream, LogLevel, Logger, MinifiError, OnTriggerResult, OutputStream, ProcessContext,
-    ProcessSession, Processor, Relationship, Schedule,
+    CalculateMetrics, Concurrent, DynamicRelationship, Exclusive, GetAttribute,
+    GetControllerService, GetProperty, InputStream, Logger, MinifiError, OnTriggerResult,
+    OutputStream, ProcessContext, ProcessSession, Processor, Schedule,
 };
 use std::collections::HashMap;
 
 pub struct TransformStreamResult {
-    target_relationship_name: &'static str,
+    target_relationship: DynamicRelationship,
     attributes_to_add: HashMap<String, String>,
+    attributes_to_remove: Vec<String>,
     write_status: IoState,
 }
 
 impl TransformStreamResult {
-    pub fn new(
-        target_relationship: &Relationship,
+    pub fn new<R: Into<DynamicRelationship>>(
+        target_relationship: R,
         attributes_to_add: HashMap<String, String>,
     ) -> Self {
         Self {
-            target_relationship_name: target_relationship.name,
+            target_relationship: target_relationship.into(),
             attributes_to_add,
+            attributes_to_remove: Vec::new(),
             write_status: IoState::Ok,
         }
     }
 
-    pub fn route_without_changes(target_relationship: &Relationship) -> Self {
+    pub fn route_without_changes<R: Into<DynamicRelationship>>(target_relationship: R) -> Self {
         Self {
-            target_relationship_name: target_relationship.name,
+            target_relationship: target_relationship.into(),
             attributes_to_add: HashMap::new(),
+            attributes_to_remove: Vec::new(),
             write_status: IoState::Cancel,
         }
     }
 
-    pub fn target_relationship_name(&self) -> &'static str {
-        self.target_relationship_name
+    pub fn target_relationship_name(&self) -> &str {
+        self.target_relationship.name()
     }
 
     pub fn get_attribute(&self, name: &str) -> Option<String> {
@@ -46,9 +50,26 @@ impl TransformStreamResult {
     pub fn write_status(&self) -> IoState {
         self.write_status
     }
+
+    /// Attributes removed from the flow file before attributes_to_add are set
+    pub fn with_attributes_to_remove<I, S>(mut self, attributes_to_remove: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.attributes_to_remove = attributes_to_remove.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn attributes_to_remove(&self) -> &[String] {
+        &self.attributes_to_remove
+    }
 }
 
 pub trait FlowFileStreamTransform {
+    /// How many flow files are transformed per on_trigger, override to drain the input in batches
+    const TRANSFORM_BATCH: TransformBatch = TransformBatch::SINGLE;
+
     fn transform<Ctx: GetProperty + GetControllerService + GetAttribute, LoggerImpl: Logger>(
         &self,
         context: &Ctx,
@@ -59,6 +80,9 @@ pub trait FlowFileStreamTransform {
 }
 
 pub trait MutFlowFileStreamTransform {
+    /// How many flow files are transformed per on_trigger, override to drain the input in batches
+    const TRANSFORM_BATCH: TransformBatch = TransformBatch::SINGLE;
+
     fn transform<Ctx: GetProperty + GetControllerService + GetAttribute, LoggerImpl: Logger>(
         &mut self,
         context: &Ctx,
@@ -74,6 +98,7 @@ fn handle_stream_transform<PC, PS, L, F>(
     context: &mut PC,
     session: &mut PS,
     logger: &L,
+    batch: TransformBatch,
     mut transform_fn: F,
 ) -> Result<OnTriggerResult, MinifiError>
 where
@@ -86,34 +111,32 @@ where
         &mut dyn OutputStream,
     ) -> Result<TransformStreamResult, MinifiError>,
 {
-    if let Some(mut flow_file) = session.get() {
-        let simple_context = ContextSessionFlowFileBundle::new(context, session, Some(&flow_file));
-
-        let (relationship, attrs) = session.read_stream(&flow_file, |input_stream| {
-            session.write_stream(&flow_file, |output_stream| {
-                let transformed = transform_fn(&simple_context, input_stream, output_stream)?;
-
-                Ok((
-                    (
-                        transformed.target_relationship_name,
-                        transformed.attributes_to_add,
-                    ),
-                    transformed.write_status,
-                ))
-            })
-        })?;
-
-        for (k, v) in attrs {
-            session.set_attribute(&mut flow_file, &k, &v)?;
-        }
+    process_batch(session, batch, logger, |session, flow_file| {
+        let simple_context = ContextSessionFlowFileBundle::new(context, session, Some(flow_file));
 
-        session.transfer(flow_file, relationship)?;
+        let (relationship, attrs_to_remove, attrs_to_add) =
+            session.read_stream(flow_file, |input_stream| {
+                session.write_stream(flow_file, |output_stream| {
+                    let transformed = transform_fn(&simple_context, input_stream, output_stream)?;
 
-        Ok(OnTriggerResult::Ok)
-    } else {
-        logger.log(LogLevel::Trace, format_args!("No flowfile to transform"));
-        Ok(OnTriggerResult::Yield)
-    }
+                    Ok((
+                        (
+                            transformed.target_relationship,
+                            transformed.attributes_to_remove,
+                            transformed.attributes_to_add,
+                        ),
+                        transformed.write_status,
+                    ))
+                })
+            })?;
+
+        for k in attrs_to_remove {
+            session.remove_attribute(flow_file, &k)?;
+        }
+        session.set_attributes(flow_file, attrs_to_add)?;
+
+        Ok(Some(relationship))
+    })
 }
 
 // Concurrent Implementation (Multi-Threaded)
@@ -124,6 +147,9 @@ where
         Schedule + CalculateMetrics + FlowFileStreamTransform + AdvancedProcessorFeatures,
     L: Logger,
 {
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        Implementation::TRANSFORM_BATCH.failure_relationship_name();
+
     fn on_trigger<PC, PS>(
         &self,
         context: &mut PC,
@@ -134,9 +160,13 @@ where
         PS: ProcessSession<FlowFile = PC::FlowFile>,
     {
         if let Some(ref scheduled_impl) = self.scheduled_impl {
-            handle_stream_transform(context, session, &self.logger, |ctx, input, output| {
-                scheduled_impl.transform(ctx, input, output, &self.logger)
-            })
+            handle_stream_transform(
+                context,
+                session,
+                &self.logger,
+                Implementation::TRANSFORM_BATCH,
+                |ctx, input, output| scheduled_impl.transform(ctx, input, output, &self.logger),
+            )
         } else {
             Err(MinifiError::trigger_err(
                 "The processor hasn't been scheduled yet",
@@ -153,6 +183,9 @@ where
         Schedule + CalculateMetrics + MutFlowFileStreamTransform + AdvancedProcessorFeatures,
     L: Logger,
 {
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        Implementation::TRANSFORM_BATCH.failure_relationship_name();
+
     fn on_trigger<PC, PS>(
         &mut self,
         context: &mut PC,
@@ -163,9 +196,13 @@ where
         PS: ProcessSession<FlowFile = PC::FlowFile>,
     {
         if let Some(ref mut scheduled_impl) = self.scheduled_impl {
-            handle_stream_transform(context, session, &self.logger, |ctx, input, output| {
-                scheduled_impl.transform(ctx, input, output, &self.logger)
-            })
+            handle_stream_transform(
+                context,
+                session,
+                &self.logger,
+                Implementation::TRANSFORM_BATCH,
+                |ctx, input, output| scheduled_impl.transform(ctx, input, output, &self.logger),
+            )
         } else {
             Err(MinifiError::trigger_err(
                 "The processor hasn't been scheduled yet",
@@ -173,3 +210,53 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::test_processor::{SUCCESS, scheduled, session_with, test_processor};
+
+    test_processor!(Reverse);
+
+    impl MutFlowFileStreamTransform for Reverse {
+        fn transform<Ctx: GetProperty + GetControllerService + GetAttribute, LoggerImpl: Logger>(
+            &mut self,
+            _context: &Ctx,
+            input_stream: &mut dyn InputStream,
+            output_stream: &mut dyn OutputStream,
+            _logger: &LoggerImpl,
+        ) -> Result<TransformStreamResult, MinifiError> {
+            let mut content = Vec::new();
+            input_stream.read_to_end(&mut content)?;
+            content.reverse();
+            output_stream.write_all(&content)?;
+            Ok(TransformStreamResult::new(
+                &SUCCESS,
+                HashMap::from([("reversed".to_string(), "true".to_string())]),
+            )
+            .with_attributes_to_remove(["reversed", "checksum"]))
+        }
+    }
+
+    #[test]
+    fn attributes_are_removed_before_the_new_ones_are_set() {
+        let (mut processor, mut context) =
+            scheduled::<Processor<Reverse, FlowFileStreamTransformProcessorType, Exclusive, _>>();
+        let mut session = session_with(
+            b"abc",
+            &[
+                ("reversed", "false"),
+                ("checksum", "1234"),
+                ("filename", "a"),
+            ],
+        );
+        processor.on_trigger(&mut context, &mut session).unwrap();
+
+        let transferred = session.transferred_flow_files.borrow();
+        let flow_file = &transferred[0].flow_file;
+        assert!(flow_file.content_eq("cba"));
+        assert_eq!(flow_file.attributes.get("reversed").unwrap(), "true");
+        assert!(!flow_file.attributes.contains_key("checksum"));
+        assert_eq!(flow_file.attributes.get("filename").unwrap(), "a");
+    }
+}
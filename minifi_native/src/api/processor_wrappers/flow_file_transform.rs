@@ -2,38 +2,42 @@ use crate::api::InputStream;
 use crate::api::processor::{AdvancedProcessorFeatures, Processor};
 use crate::api::processor_wrappers::utils::context_session_flowfile_bundle::ContextSessionFlowFileBundle;
 use crate::api::processor_wrappers::utils::flow_file_content::Content;
+use crate::api::processor_wrappers::utils::transform_batch::{TransformBatch, process_batch};
 use crate::api::property::{GetControllerService, GetProperty};
 use crate::api::raw_processor::{MultiThreadedTrigger, SingleThreadedTrigger};
 use crate::{
-    CalculateMetrics, Concurrent, Exclusive, GetAttribute, LogLevel, Logger, MinifiError,
-    OnTriggerResult, ProcessContext, ProcessSession, Relationship, Schedule,
+    CalculateMetrics, Concurrent, DynamicRelationship, Exclusive, GetAttribute, Logger,
+    MinifiError, OnTriggerResult, ProcessContext, ProcessSession, Schedule,
 };
 use std::collections::HashMap;
 
 pub struct TransformedFlowFile<'a> {
-    target_relationship_name: &'static str,
+    target_relationship: DynamicRelationship,
     new_content: Option<Content<'a>>,
     attributes_to_add: HashMap<String, String>,
+    attributes_to_remove: Vec<String>,
 }
 
 impl<'a> TransformedFlowFile<'a> {
-    pub fn route_without_changes(target_relationship: &Relationship) -> Self {
+    pub fn route_without_changes<R: Into<DynamicRelationship>>(target_relationship: R) -> Self {
         Self {
-            target_relationship_name: target_relationship.name,
+            target_relationship: target_relationship.into(),
             new_content: None,
             attributes_to_add: HashMap::new(),
+            attributes_to_remove: Vec::new(),
         }
     }
 
-    pub fn new(
-        target_relationship: &Relationship,
+    pub fn new<R: Into<DynamicRelationship>>(
+        target_relationship: R,
         new_content: Option<Vec<u8>>,
         attributes_to_add: HashMap<String, String>,
     ) -> Self {
         Self {
-            target_relationship_name: target_relationship.name,
+            target_relationship: target_relationship.into(),
             new_content: Some(Content::Buffer(new_content.unwrap_or_default())),
             attributes_to_add,
+            attributes_to_remove: Vec::new(),
         }
     }
 
@@ -41,16 +45,33 @@ impl<'a> TransformedFlowFile<'a> {
         self.new_content.as_ref()
     }
 
-    pub fn target_relationship(&self) -> &'static str {
-        self.target_relationship_name
+    pub fn target_relationship(&self) -> &str {
+        self.target_relationship.name()
     }
 
     pub fn attributes_to_add(&self) -> &HashMap<String, String> {
         &self.attributes_to_add
     }
+
+    /// Attributes removed from the flow file before attributes_to_add are set
+    pub fn with_attributes_to_remove<I, S>(mut self, attributes_to_remove: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.attributes_to_remove = attributes_to_remove.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn attributes_to_remove(&self) -> &[String] {
+        &self.attributes_to_remove
+    }
 }
 
 pub trait FlowFileTransform {
+    /// How many flow files are transformed per on_trigger, override to drain the input in batches
+    const TRANSFORM_BATCH: TransformBatch = TransformBatch::SINGLE;
+
     fn transform<
         'a,
         Context: GetProperty + GetControllerService + GetAttribute,
@@ -64,6 +85,9 @@ pub trait FlowFileTransform {
 }
 
 pub trait MutFlowFileTransform {
+    /// How many flow files are transformed per on_trigger, override to drain the input in batches
+    const TRANSFORM_BATCH: TransformBatch = TransformBatch::SINGLE;
+
     fn transform<
         'a,
         Context: GetProperty + GetControllerService + GetAttribute,
@@ -82,6 +106,7 @@ fn handle_transform<PC, PS, L, F>(
     context: &mut PC,
     session: &mut PS,
     logger: &L,
+    batch: TransformBatch,
     mut transform_fn: F,
 ) -> Result<OnTriggerResult, MinifiError>
 where
@@ -93,38 +118,37 @@ where
         &'stream mut dyn InputStream,
     ) -> Result<TransformedFlowFile<'stream>, MinifiError>,
 {
-    if let Some(mut flow_file) = session.get() {
-        let simple_context = ContextSessionFlowFileBundle::new(context, session, Some(&flow_file));
-
-        let (attrs_to_add, relationship) = session.read_stream(&flow_file, |input_stream| {
-            let transformed = transform_fn(&simple_context, input_stream)?;
-
-            match transformed.new_content {
-                None => {}
-                Some(Content::Buffer(buffer)) => {
-                    session.write(&flow_file, &buffer)?;
-                }
-                Some(Content::Stream(stream)) => {
-                    session.write_lazy(&flow_file, stream)?;
-                }
-            };
-
-            Ok((
-                transformed.attributes_to_add,
-                transformed.target_relationship_name,
-            ))
-        })?;
+    process_batch(session, batch, logger, |session, flow_file| {
+        let simple_context = ContextSessionFlowFileBundle::new(context, session, Some(flow_file));
+
+        let (attrs_to_remove, attrs_to_add, relationship) =
+            session.read_stream(flow_file, |input_stream| {
+                let transformed = transform_fn(&simple_context, input_stream)?;
+
+                match transformed.new_content {
+                    None => {}
+                    Some(Content::Buffer(buffer)) => {
+                        session.write(flow_file, &buffer)?;
+                    }
+                    Some(Content::Stream(stream)) => {
+                        session.write_lazy(flow_file, stream)?;
+                    }
+                };
 
-        for (k, v) in attrs_to_add {
-            session.set_attribute(&mut flow_file, &k, &v)?;
+                Ok((
+                    transformed.attributes_to_remove,
+                    transformed.attributes_to_add,
+                    transformed.target_relationship,
+                ))
+            })?;
+
+        for k in attrs_to_remove {
+            session.remove_attribute(flow_file, &k)?;
         }
+        session.set_attributes(flow_file, attrs_to_add)?;
 
-        session.transfer(flow_file, relationship)?;
-        Ok(OnTriggerResult::Ok)
-    } else {
-        logger.log(LogLevel::Trace, format_args!("No flowfile to transform"));
-        Ok(OnTriggerResult::Yield)
-    }
+        Ok(Some(relationship))
+    })
 }
 
 impl<Implementation, L> MultiThreadedTrigger
@@ -133,6 +157,9 @@ where
     Implementation: Schedule + CalculateMetrics + FlowFileTransform + AdvancedProcessorFeatures,
     L: Logger,
 {
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        Implementation::TRANSFORM_BATCH.failure_relationship_name();
+
     fn on_trigger<PC, PS>(
         &self,
         context: &mut PC,
@@ -143,9 +170,13 @@ where
         PS: ProcessSession<FlowFile = PC::FlowFile>,
     {
         if let Some(ref scheduled_impl) = self.scheduled_impl {
-            handle_transform(context, session, &self.logger, |ctx, input| {
-                scheduled_impl.transform(ctx, input, &self.logger)
-            })
+            handle_transform(
+                context,
+                session,
+                &self.logger,
+                Implementation::TRANSFORM_BATCH,
+                |ctx, input| scheduled_impl.transform(ctx, input, &self.logger),
+            )
         } else {
             Err(MinifiError::trigger_err(
                 "The processor hasn't been scheduled yet",
@@ -160,6 +191,9 @@ where
     Implementation: Schedule + CalculateMetrics + MutFlowFileTransform + AdvancedProcessorFeatures,
     L: Logger,
 {
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        Implementation::TRANSFORM_BATCH.failure_relationship_name();
+
     fn on_trigger<PC, PS>(
         &mut self,
         context: &mut PC,
@@ -170,9 +204,13 @@ where
         PS: ProcessSession<FlowFile = PC::FlowFile>,
     {
         if let Some(ref mut scheduled_impl) = self.scheduled_impl {
-            handle_transform(context, session, &self.logger, |ctx, input| {
-                scheduled_impl.transform(ctx, input, &self.logger)
-            })
+            handle_transform(
+                context,
+                session,
+                &self.logger,
+                Implementation::TRANSFORM_BATCH,
+                |ctx, input| scheduled_impl.transform(ctx, input, &self.logger),
+            )
         } else {
             Err(MinifiError::trigger_err(
                 "The processor hasn't been scheduled yet",
@@ -180,3 +218,77 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::test_processor::{FAILURE, SUCCESS, scheduled, session_with, test_processor};
+    use crate::{MockFlowFile, TransformBatch};
+
+    test_processor!(Uppercase);
+
+    impl FlowFileTransform for Uppercase {
+        const TRANSFORM_BATCH: TransformBatch = TransformBatch::up_to(10, &FAILURE);
+
+        fn transform<
+            'a,
+            Context: GetProperty + GetControllerService + GetAttribute,
+            LoggerImpl: Logger,
+        >(
+            &self,
+            _context: &Context,
+            input_stream: &'a mut dyn InputStream,
+            _logger: &LoggerImpl,
+        ) -> Result<TransformedFlowFile<'a>, MinifiError> {
+            let mut content = Vec::new();
+            input_stream.read_to_end(&mut content)?;
+            if !content.is_ascii() {
+                return Err(MinifiError::trigger_err("not ascii"));
+            }
+            Ok(TransformedFlowFile::new(
+                &SUCCESS,
+                Some(content.to_ascii_uppercase()),
+                HashMap::from([("case".to_string(), "upper".to_string())]),
+            )
+            .with_attributes_to_remove(["case", "http.status"]))
+        }
+    }
+
+    #[test]
+    fn attributes_are_removed_before_the_new_ones_are_set() {
+        let (processor, mut context) =
+            scheduled::<Processor<Uppercase, FlowFileTransformProcessorType, Concurrent, _>>();
+        let mut session = session_with(
+            b"hello",
+            &[("case", "lower"), ("http.status", "200"), ("filename", "a")],
+        );
+        processor.on_trigger(&mut context, &mut session).unwrap();
+
+        let transferred = session.transferred_flow_files.borrow();
+        let flow_file = &transferred[0].flow_file;
+        assert!(flow_file.content_eq("HELLO"));
+        assert_eq!(flow_file.attributes.get("case").unwrap(), "upper");
+        assert!(!flow_file.attributes.contains_key("http.status"));
+        assert_eq!(flow_file.attributes.get("filename").unwrap(), "a");
+    }
+
+    #[test]
+    fn failing_flow_file_is_routed_to_failure() {
+        let (processor, mut context) =
+            scheduled::<Processor<Uppercase, FlowFileTransformProcessorType, Concurrent, _>>();
+        let mut session = session_with("grüß".as_bytes(), &[]);
+        session
+            .input_flow_files
+            .push(MockFlowFile::with_content(b"hello"));
+        processor.on_trigger(&mut context, &mut session).unwrap();
+
+        assert_eq!(session.num_of_flow_files_transferred_to(SUCCESS.name), 1);
+        assert_eq!(session.num_of_flow_files_transferred_to(FAILURE.name), 1);
+        let transferred = session.transferred_flow_files.borrow();
+        let failed = transferred
+            .iter()
+            .find(|transferred| transferred.relationship == FAILURE.name)
+            .unwrap();
+        assert!(failed.flow_file.content_eq("grüß"));
+    }
+}
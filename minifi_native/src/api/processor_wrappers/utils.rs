@@ -1,2 +1,3 @@
 pub(crate) mod context_session_flowfile_bundle;
 pub(crate) mod flow_file_content;
+pub(crate) mod transform_batch;
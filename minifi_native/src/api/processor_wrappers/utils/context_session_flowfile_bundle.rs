@@ -53,6 +53,13 @@ where
     {
         self.context.get_controller_service(property)
     }
+
+    fn get_controller_service_dyn<I>(&self, property: &Property) -> Result<Option<&I>, MinifiError>
+    where
+        I: ?Sized + 'static,
+    {
+        self.context.get_controller_service_dyn(property)
+    }
 }
 
 impl<'a, PC, PS> GetAttribute for ContextSessionFlowFileBundle<'a, PC, PS>
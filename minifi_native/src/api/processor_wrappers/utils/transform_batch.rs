@@ -0,0 +1,333 @@
+use crate::{
+    DynamicRelationship, FlowFile, LogLevel, Logger, MinifiError, OnTriggerResult, ProcessSession,
+    Relationship,
+};
+use std::time::{Duration, Instant};
+
+/// Limits how many flow files a transform wrapper processes in a single on_trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransformBatch {
+    max_flow_files: usize,
+    time_budget: Option<Duration>,
+    failure_relationship: Option<&'static str>,
+}
+
+impl TransformBatch {
+    /// One flow file per on_trigger, a failing flow file fails the trigger and the session is rolled back
+    pub const SINGLE: TransformBatch = TransformBatch {
+        max_flow_files: 1,
+        time_budget: None,
+        failure_relationship: None,
+    };
+
+    /// At most max_flow_files per on_trigger (at least one flow file is always processed)
+    ///
+    /// A flow file that fails to transform is routed unchanged to failure_relationship,
+    /// so it doesn't roll back the rest of the batch.
+    /// Registering the processor fails to compile if it isn't one of the processor's relationships.
+    ///
+    /// ```compile_fail,E0080
+    /// use minifi_native::c_ffi::{CffiLogger, RawRegisterableProcessor};
+    /// use minifi_native::macros::{
+    ///     ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition,
+    /// };
+    /// use minifi_native::{
+    ///     Concurrent, FlowFileTransform, FlowFileTransformProcessorType, GetAttribute,
+    ///     GetControllerService, GetProperty, InputStream, Logger, MinifiError, Processor,
+    ///     Relationship, Schedule, TransformBatch, TransformedFlowFile,
+    /// };
+    ///
+    /// const SUCCESS: Relationship = Relationship {
+    ///     name: "success",
+    ///     description: "",
+    /// };
+    ///
+    /// const FAILURE: Relationship = Relationship {
+    ///     name: "failure",
+    ///     description: "",
+    /// };
+    ///
+    /// #[derive(ComponentIdentifier, DefaultMetrics, NoAdvancedProcessorFeatures, ProcessorDefinition)]
+    /// #[processor(description = "Passes flow files through", relationships = [SUCCESS])]
+    /// struct PassThrough;
+    ///
+    /// impl Schedule for PassThrough {
+    ///     fn schedule<P: GetProperty, L: Logger>(_context: &P, _logger: &L) -> Result<Self, MinifiError> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// impl FlowFileTransform for PassThrough {
+    ///     const TRANSFORM_BATCH: TransformBatch = TransformBatch::up_to(10, &FAILURE);
+    ///
+    ///     fn transform<'a, Ctx: GetProperty + GetControllerService + GetAttribute, L: Logger>(
+    ///         &self,
+    ///         _context: &Ctx,
+    ///         _input_stream: &'a mut dyn InputStream,
+    ///         _logger: &L,
+    ///     ) -> Result<TransformedFlowFile<'a>, MinifiError> {
+    ///         Ok(TransformedFlowFile::route_without_changes(&SUCCESS))
+    ///     }
+    /// }
+    ///
+    /// const _: () = <Processor<PassThrough, FlowFileTransformProcessorType, Concurrent, CffiLogger>
+    ///     as RawRegisterableProcessor>::RELATIONSHIPS_CHECK;
+    /// ```
+    pub const fn up_to(max_flow_files: usize, failure_relationship: &'static Relationship) -> Self {
+        Self {
+            max_flow_files,
+            time_budget: None,
+            failure_relationship: Some(failure_relationship.name),
+        }
+    }
+
+    /// Stops taking new flow files once the time budget is spent, the current one is always finished
+    pub const fn with_time_budget(self, time_budget: Duration) -> Self {
+        Self {
+            time_budget: Some(time_budget),
+            ..self
+        }
+    }
+
+    pub const fn max_flow_files(&self) -> usize {
+        self.max_flow_files
+    }
+
+    pub const fn time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    pub const fn failure_relationship_name(&self) -> Option<&'static str> {
+        self.failure_relationship
+    }
+
+    fn should_continue(&self, processed: usize, started: Instant) -> bool {
+        processed == 0
+            || (processed < self.max_flow_files
+                && self
+                    .time_budget
+                    .is_none_or(|budget| started.elapsed() < budget))
+    }
+}
+
+impl Default for TransformBatch {
+    fn default() -> Self {
+        Self::SINGLE
+    }
+}
+
+/// Calls process_fn for each incoming flow file until the batch is full or the session runs dry,
+/// the flow file is transferred to the returned relationship or removed if it is None.
+///
+/// A failing flow file is logged and routed to the failure relationship of the batch,
+/// without one it fails the whole trigger and the session is rolled back.
+pub(crate) fn process_batch<PS, L, F>(
+    session: &mut PS,
+    batch: TransformBatch,
+    logger: &L,
+    mut process_fn: F,
+) -> Result<OnTriggerResult, MinifiError>
+where
+    PS: ProcessSession,
+    L: Logger,
+    F: FnMut(&mut PS, &mut PS::FlowFile) -> Result<Option<DynamicRelationship>, MinifiError>,
+{
+    let started = Instant::now();
+    let mut processed = 0;
+    while batch.should_continue(processed, started) {
+        let Some(mut flow_file) = session.get() else {
+            break;
+        };
+        match process_fn(session, &mut flow_file) {
+            Ok(Some(relationship)) => session.transfer(flow_file, relationship.name())?,
+            Ok(None) => session.remove(flow_file)?,
+            Err(err) => {
+                let err = match flow_file.uuid() {
+                    Some(flow_file_uuid) => err.for_flow_file(flow_file_uuid),
+                    None => err,
+                };
+                let Some(failure_relationship) = batch.failure_relationship else {
+                    return Err(err);
+                };
+                logger.log(LogLevel::Error, format_args!("{}", err.report()));
+                session.transfer(flow_file, failure_relationship)?;
+            }
+        }
+        processed += 1;
+    }
+
+    if processed == 0 {
+        logger.log(LogLevel::Trace, format_args!("No flowfile to transform"));
+        Ok(OnTriggerResult::Yield)
+    } else {
+        logger.log(
+            LogLevel::Trace,
+            format_args!("Transformed {processed} flow files"),
+        );
+        Ok(OnTriggerResult::Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockFlowFile, MockLogger, MockProcessSession};
+
+    const SUCCESS: Relationship = Relationship {
+        name: "success",
+        description: "",
+    };
+
+    const FAILURE: Relationship = Relationship {
+        name: "failure",
+        description: "",
+    };
+
+    fn session_with(num_of_flow_files: usize) -> MockProcessSession {
+        let mut session = MockProcessSession::new();
+        for _ in 0..num_of_flow_files {
+            session.input_flow_files.push(MockFlowFile::new());
+        }
+        session
+    }
+
+    #[test]
+    fn single_processes_one_flow_file() {
+        let mut session = session_with(3);
+        let result = process_batch(
+            &mut session,
+            TransformBatch::SINGLE,
+            &MockLogger::new(),
+            |_, _| Ok(Some((&SUCCESS).into())),
+        );
+        assert_eq!(result.unwrap(), OnTriggerResult::Ok);
+        assert_eq!(session.num_of_transferred_flow_files(), 1);
+        assert_eq!(session.input_flow_files.len(), 2);
+    }
+
+    #[test]
+    fn batch_drains_until_empty_or_full() {
+        let mut session = session_with(3);
+        let result = process_batch(
+            &mut session,
+            TransformBatch::up_to(10, &FAILURE),
+            &MockLogger::new(),
+            |_, _| Ok(Some((&SUCCESS).into())),
+        );
+        assert_eq!(result.unwrap(), OnTriggerResult::Ok);
+        assert_eq!(session.num_of_transferred_flow_files(), 3);
+
+        let mut session = session_with(5);
+        process_batch(
+            &mut session,
+            TransformBatch::up_to(2, &FAILURE),
+            &MockLogger::new(),
+            |_, _| Ok(Some((&SUCCESS).into())),
+        )
+        .unwrap();
+        assert_eq!(session.num_of_transferred_flow_files(), 2);
+        assert_eq!(session.input_flow_files.len(), 3);
+    }
+
+    #[test]
+    fn exhausted_time_budget_still_processes_one() {
+        let mut session = session_with(3);
+        process_batch(
+            &mut session,
+            TransformBatch::up_to(10, &FAILURE).with_time_budget(Duration::ZERO),
+            &MockLogger::new(),
+            |_, _| Ok(Some((&SUCCESS).into())),
+        )
+        .unwrap();
+        assert_eq!(session.num_of_transferred_flow_files(), 1);
+    }
+
+    #[test]
+    fn yields_without_input() {
+        let mut session = session_with(0);
+        let result = process_batch(
+            &mut session,
+            TransformBatch::up_to(10, &FAILURE),
+            &MockLogger::new(),
+            |_, _| Ok(Some((&SUCCESS).into())),
+        );
+        assert_eq!(result.unwrap(), OnTriggerResult::Yield);
+    }
+
+    #[test]
+    fn failing_flow_file_is_routed_to_failure() {
+        let mut session = session_with(5);
+        let logger = MockLogger::new();
+        let mut calls = 0;
+        let result = process_batch(
+            &mut session,
+            TransformBatch::up_to(10, &FAILURE),
+            &logger,
+            |_, _| {
+                calls += 1;
+                if calls == 2 {
+                    return Err(MinifiError::trigger_err("boom"));
+                }
+                Ok(Some((&SUCCESS).into()))
+            },
+        );
+        assert_eq!(result.unwrap(), OnTriggerResult::Ok);
+        assert_eq!(calls, 5);
+        assert_eq!(session.num_of_flow_files_transferred_to(SUCCESS.name), 4);
+        assert_eq!(session.num_of_flow_files_transferred_to(FAILURE.name), 1);
+        let logs = logger.logs.lock().unwrap();
+        assert!(logs.iter().any(|(level, _)| *level == LogLevel::Error));
+    }
+
+    #[test]
+    fn failure_fails_a_single_flow_file_trigger() {
+        let mut session = session_with(2);
+        let result = process_batch(
+            &mut session,
+            TransformBatch::SINGLE,
+            &MockLogger::new(),
+            |_, _| Err(MinifiError::trigger_err("boom")),
+        );
+        assert!(result.is_err());
+        assert_eq!(session.num_of_transferred_flow_files(), 0);
+        assert_eq!(session.input_flow_files.len(), 1);
+    }
+
+    #[test]
+    fn none_removes_the_flow_file() {
+        let mut session = session_with(1);
+        process_batch(
+            &mut session,
+            TransformBatch::SINGLE,
+            &MockLogger::new(),
+            |_, _| Ok(None),
+        )
+        .unwrap();
+        assert_eq!(session.num_of_transferred_flow_files(), 0);
+        assert!(session.input_flow_files.is_empty());
+    }
+
+    #[test]
+    fn failure_carries_the_flow_file_uuid() {
+        let mut session = MockProcessSession::new();
+        let mut flow_file = MockFlowFile::new();
+        flow_file
+            .attributes
+            .insert("uuid".to_string(), "0000-1111".to_string());
+        session.input_flow_files.push(flow_file);
+
+        let error = process_batch(
+            &mut session,
+            TransformBatch::SINGLE,
+            &MockLogger::new(),
+            |_, _| Err(MinifiError::trigger_err("boom")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error
+                .context()
+                .and_then(|context| context.flow_file_uuid.as_deref()),
+            Some("0000-1111")
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use crate::StandardPropertyValidator::{
-    BoolValidator, DataSizeValidator, TimePeriodValidator, U64Validator,
+    AlwaysValidValidator, BoolValidator, DataSizeValidator, I64Validator, TimePeriodValidator,
+    U64Validator,
 };
 use crate::{ComponentIdentifier, EnableControllerService, MinifiError};
 use std::str::FromStr;
 use std::time::Duration;
+use strum::VariantNames;
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum StandardPropertyValidator {
@@ -30,6 +32,16 @@ pub struct Property {
     pub allowed_type: &'static str,
 }
 
+/// Describes the dynamic properties a processor accepts, name and value are human-readable
+/// descriptions (e.g. "Attribute Name" and "Attribute Expression") rather than actual keys
+#[derive(Debug)]
+pub struct DynamicProperty {
+    pub name: &'static str,
+    pub value: &'static str,
+    pub description: &'static str,
+    pub supports_expr_lang: bool,
+}
+
 pub trait GetProperty {
     fn get_property(&self, property: &Property) -> Result<Option<String>, MinifiError>;
     fn get_bool_property(&self, property: &Property) -> Result<Option<bool>, MinifiError> {
@@ -89,10 +101,229 @@ pub trait GetProperty {
             Ok(None)
         }
     }
+
+    fn get_enum_property<E: FromStr + VariantNames>(
+        &self,
+        property: &Property,
+    ) -> Result<Option<E>, MinifiError> {
+        parse_enum_property(property, self.get_property(property)?)
+    }
+
+    fn get_typed_property<T: PropertyValue>(
+        &self,
+        property: &Property,
+    ) -> Result<Option<T>, MinifiError> {
+        if let Some(property_val) = self.get_property(property)? {
+            Ok(Some(T::parse_property_value(&property_val)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+pub(crate) fn parse_enum_property<E: FromStr + VariantNames>(
+    property: &Property,
+    property_val: Option<String>,
+) -> Result<Option<E>, MinifiError> {
+    if property.allowed_values != E::VARIANTS {
+        return Err(MinifiError::validation_err(format!(
+            "to use get_enum_property {:?} must have {:?} as allowed_values",
+            property,
+            E::VARIANTS
+        )));
+    }
+
+    let Some(property_val) = property_val else {
+        return Ok(None);
+    };
+    let not_allowed_err = || {
+        MinifiError::validation_err(format!(
+            "{:?} is not a valid value for {:?}, allowed values are {:?}",
+            property_val, property.name, property.allowed_values
+        ))
+    };
+    if !property.allowed_values.contains(&property_val.as_str()) {
+        return Err(not_allowed_err());
+    }
+    E::from_str(&property_val)
+        .map(Some)
+        .map_err(|_| not_allowed_err())
+}
+
+/// Types that a property value can be parsed into, used by #[derive(Properties)]
+/// to pick the validator and allowed values of the generated Property
+pub trait PropertyValue: Sized {
+    const VALIDATOR: StandardPropertyValidator;
+    const ALLOWED_VALUES: &'static [&'static str] = &[];
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError>;
+}
+
+/// Marker for strum enums (EnumString + VariantNames) so they can be used as property values,
+/// the variants become the allowed values of the property
+pub trait EnumPropertyValue: FromStr<Err = strum::ParseError> + VariantNames {}
+
+/// A property value validated by DataSizeValidator (e.g. "10 kB"), in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DataSize(pub u64);
+
+impl DataSize {
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl PropertyValue for String {
+    const VALIDATOR: StandardPropertyValidator = AlwaysValidValidator;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(value.to_string())
+    }
+}
+
+impl PropertyValue for bool {
+    const VALIDATOR: StandardPropertyValidator = BoolValidator;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(bool::from_str(value)?)
+    }
+}
+
+impl PropertyValue for u64 {
+    const VALIDATOR: StandardPropertyValidator = U64Validator;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(u64::from_str(value)?)
+    }
+}
+
+impl PropertyValue for i64 {
+    const VALIDATOR: StandardPropertyValidator = I64Validator;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(i64::from_str(value)?)
+    }
+}
+
+impl PropertyValue for Duration {
+    const VALIDATOR: StandardPropertyValidator = TimePeriodValidator;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(humantime::parse_duration(value)?)
+    }
+}
+
+impl PropertyValue for DataSize {
+    const VALIDATOR: StandardPropertyValidator = DataSizeValidator;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(DataSize(byte_unit::Byte::from_str(value)?.as_u64()))
+    }
+}
+
+impl<T: EnumPropertyValue> PropertyValue for T {
+    const VALIDATOR: StandardPropertyValidator = AlwaysValidValidator;
+    const ALLOWED_VALUES: &'static [&'static str] = T::VARIANTS;
+
+    fn parse_property_value(value: &str) -> Result<Self, MinifiError> {
+        Ok(T::from_str(value)?)
+    }
+}
+
+/// A group of properties with a loader, usually implemented with #[derive(Properties)]
+pub trait Properties: Sized {
+    const PROPERTIES: &'static [Property];
+
+    fn from_context<Ctx: GetProperty>(context: &Ctx) -> Result<Self, MinifiError>;
 }
 
 pub trait GetControllerService {
     fn get_controller_service<Cs>(&self, property: &Property) -> Result<Option<&Cs>, MinifiError>
     where
         Cs: EnableControllerService + ComponentIdentifier + 'static;
+
+    /// Looks up the service by one of its interfaces e.g. `get_controller_service_dyn::<dyn KeyProvider>`,
+    /// None if the service doesn't provide I (see EnableControllerService::provide_interfaces)
+    fn get_controller_service_dyn<I>(&self, property: &Property) -> Result<Option<&I>, MinifiError>
+    where
+        I: ?Sized + 'static;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        GetProperty, LogLevel, MinifiError, MockProcessContext, Property, StandardPropertyValidator,
+    };
+    use strum::VariantNames;
+
+    const LOG_LEVEL: Property = Property {
+        name: "Log Level",
+        description: "",
+        is_required: true,
+        is_sensitive: false,
+        supports_expr_lang: false,
+        default_value: Some("Info"),
+        validator: StandardPropertyValidator::AlwaysValidValidator,
+        allowed_values: LogLevel::VARIANTS,
+        allowed_type: "",
+    };
+
+    #[test]
+    fn get_enum_property_test() {
+        let mut context = MockProcessContext::new();
+        assert_eq!(
+            context.get_enum_property::<LogLevel>(&LOG_LEVEL).unwrap(),
+            Some(LogLevel::Info)
+        );
+
+        context.properties.insert(LOG_LEVEL.name, "Warn");
+        assert_eq!(
+            context.get_enum_property::<LogLevel>(&LOG_LEVEL).unwrap(),
+            Some(LogLevel::Warn)
+        );
+
+        context.properties.insert(LOG_LEVEL.name, "Loud");
+        assert!(matches!(
+            context.get_enum_property::<LogLevel>(&LOG_LEVEL),
+            Err(MinifiError::ValidationError(msg)) if msg.contains("Critical")
+        ));
+    }
+
+    #[test]
+    fn missing_required_property() {
+        let batch_size = Property {
+            name: "Batch Size",
+            default_value: None,
+            validator: StandardPropertyValidator::U64Validator,
+            allowed_values: &[],
+            ..LOG_LEVEL
+        };
+        let mut context = MockProcessContext::new();
+        assert!(matches!(
+            GetProperty::get_property(&context, &batch_size),
+            Err(MinifiError::MissingRequiredProperty("Batch Size"))
+        ));
+        assert!(matches!(
+            context.get_typed_property::<u64>(&batch_size),
+            Err(MinifiError::MissingRequiredProperty("Batch Size"))
+        ));
+
+        context.properties.insert(batch_size.name, "10");
+        assert_eq!(
+            context.get_typed_property::<u64>(&batch_size).unwrap(),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn get_enum_property_with_mismatched_allowed_values() {
+        let property = Property {
+            allowed_values: &["Info", "Warn"],
+            ..LOG_LEVEL
+        };
+        assert!(matches!(
+            MockProcessContext::new().get_enum_property::<LogLevel>(&property),
+            Err(MinifiError::ValidationError(_))
+        ));
+    }
 }
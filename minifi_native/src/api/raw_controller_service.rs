@@ -1,11 +1,24 @@
-use crate::{GetProperty, LogLevel, Logger, MinifiError};
+use crate::{
+    GetConfig, GetExtensionState, GetInstanceMetadata, GetProperty, InstanceMetadata,
+    InterfaceRequest, LogLevel, Logger, MinifiError,
+};
 
 /// This RawControllerService will be instantiated, and called on by the agent
 pub trait RawControllerService: Sized {
     type LoggerType: Logger;
 
-    fn new(logger: Self::LoggerType) -> Self;
+    fn new(metadata: InstanceMetadata, logger: Self::LoggerType) -> Self;
+    fn instance_metadata(&self) -> &InstanceMetadata;
     fn log(&self, log_level: LogLevel, args: std::fmt::Arguments);
-    fn enable<P: GetProperty>(&mut self, context: &P) -> Result<(), MinifiError>;
+    fn logger(&self) -> &Self::LoggerType;
+    /// Marks the service after a contained panic, it can not be enabled anymore
+    fn poison(&self);
+    fn is_poisoned(&self) -> bool;
+    fn enable<P: GetProperty + GetConfig + GetExtensionState + GetInstanceMetadata>(
+        &mut self,
+        context: &P,
+    ) -> Result<(), MinifiError>;
+    fn provide_interfaces<'a>(&'a self, request: &mut InterfaceRequest<'a>);
+    /// notifyStop of the agent
     fn disable(&mut self) {}
 }
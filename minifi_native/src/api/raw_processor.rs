@@ -1,5 +1,8 @@
 use crate::api::errors::MinifiError;
-use crate::{LogLevel, Logger, ProcessContext, ProcessSession};
+use crate::api::processor_metrics::ProcessorMetrics;
+use std::sync::Arc;
+
+use crate::{InstanceMetadata, LogLevel, Logger, ProcessContext, ProcessSession};
 
 pub enum ProcessorInputRequirement {
     Required,
@@ -18,14 +21,19 @@ pub trait RawProcessor: Sized {
     type Threading: ThreadingModel;
     type LoggerType: Logger;
 
-    fn new(logger: Self::LoggerType) -> Self;
-    fn restore(&self) -> bool;
+    fn new(metadata: InstanceMetadata, logger: Self::LoggerType) -> Self;
+    fn instance_metadata(&self) -> &InstanceMetadata;
     fn get_trigger_when_empty(&self) -> bool;
     fn is_work_available(&self) -> bool;
     fn log(&self, log_level: LogLevel, args: std::fmt::Arguments);
+    fn logger(&self) -> &Self::LoggerType;
+    /// Marks the processor after a contained panic, it is not scheduled or triggered anymore
+    fn poison(&self);
+    fn is_poisoned(&self) -> bool;
     fn on_schedule<P: ProcessContext>(&mut self, context: &P) -> Result<(), MinifiError>;
     fn on_unschedule(&mut self);
     fn calculate_metrics(&self) -> Vec<(String, f64)>;
+    fn processor_metrics(&self) -> &Arc<ProcessorMetrics>;
 }
 
 /// To differentiate between single and multithreaded processors
@@ -52,6 +60,10 @@ mod sealed {
 }
 
 pub trait SingleThreadedTrigger: RawProcessor<Threading = Exclusive> {
+    /// The relationship on_trigger routes failing flow files to by itself,
+    /// it has to be one of the processor's relationships
+    const FAILURE_RELATIONSHIP: Option<&'static str> = None;
+
     fn on_trigger<PC, PS>(
         &mut self,
         context: &mut PC,
@@ -63,6 +75,10 @@ pub trait SingleThreadedTrigger: RawProcessor<Threading = Exclusive> {
 }
 
 pub trait MultiThreadedTrigger: RawProcessor<Threading = Concurrent> {
+    /// The relationship on_trigger routes failing flow files to by itself,
+    /// it has to be one of the processor's relationships
+    const FAILURE_RELATIONSHIP: Option<&'static str> = None;
+
     fn on_trigger<PC, PS>(
         &self,
         context: &mut PC,
@@ -1,5 +1,39 @@
+use std::borrow::Cow;
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct Relationship {
     pub name: &'static str,
     pub description: &'static str,
 }
+
+/// Relationship that is only known at runtime (e.g. named after a dynamic property),
+/// processors using it must set SUPPORTS_DYNAMIC_RELATIONSHIPS.
+/// Static relationships convert into it, so the result types accept both.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DynamicRelationship {
+    name: Cow<'static, str>,
+}
+
+impl DynamicRelationship {
+    pub fn new<S: Into<Cow<'static, str>>>(name: S) -> Self {
+        Self { name: name.into() }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl From<&Relationship> for DynamicRelationship {
+    fn from(relationship: &Relationship) -> Self {
+        Self {
+            name: Cow::Borrowed(relationship.name),
+        }
+    }
+}
+
+impl From<&DynamicRelationship> for DynamicRelationship {
+    fn from(relationship: &DynamicRelationship) -> Self {
+        relationship.clone()
+    }
+}
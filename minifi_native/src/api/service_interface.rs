@@ -0,0 +1,68 @@
+use std::any::{Any, TypeId};
+use std::marker::PhantomData;
+
+/// A request for one service interface (a `dyn Trait`) of a controller service,
+/// answered in EnableControllerService::provide_interfaces
+pub struct InterfaceRequest<'a> {
+    requested: TypeId,
+    provided: Option<Box<dyn Any>>,
+    _lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a> InterfaceRequest<'a> {
+    pub(crate) fn new<I: ?Sized + 'static>() -> Self {
+        Self {
+            requested: TypeId::of::<I>(),
+            provided: None,
+            _lifetime: PhantomData,
+        }
+    }
+
+    /// Offers the service as interface I e.g. `request.provide::<dyn KeyProvider>(self)`,
+    /// ignored unless I is the requested interface
+    pub fn provide<I: ?Sized + 'static>(&mut self, service: &'a I) -> &mut Self {
+        if self.provided.is_none() && self.requested == TypeId::of::<I>() {
+            self.provided = Some(Box::new(service as *const I));
+        }
+        self
+    }
+
+    pub(crate) fn into_provided<I: ?Sized + 'static>(self) -> Option<&'a I> {
+        let service = *self.provided?.downcast::<*const I>().ok()?;
+        // the pointer was created from a &'a I in provide
+        unsafe { service.as_ref() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    trait Greeter {
+        fn greet(&self) -> String;
+    }
+
+    trait Counter {}
+
+    struct English;
+
+    impl Greeter for English {
+        fn greet(&self) -> String {
+            "hello".to_string()
+        }
+    }
+
+    #[test]
+    fn test_provide_requested_interface() {
+        let service = English;
+
+        let mut request = InterfaceRequest::new::<dyn Greeter>();
+        request.provide::<dyn Greeter>(&service);
+        let greeter = request.into_provided::<dyn Greeter>().unwrap();
+        assert_eq!(greeter.greet(), "hello");
+
+        let mut request = InterfaceRequest::new::<dyn Counter>();
+        request.provide::<dyn Greeter>(&service);
+        assert!(request.into_provided::<dyn Counter>().is_none());
+    }
+}
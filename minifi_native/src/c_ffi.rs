@@ -1,9 +1,14 @@
 mod c_ffi_controller_service_context;
 mod c_ffi_controller_service_definition;
 mod c_ffi_controller_service_list;
+mod c_ffi_controller_service_lookup;
+mod c_ffi_dynamic_property;
+mod c_ffi_extension_config;
+mod c_ffi_extension_state;
 mod c_ffi_flow_file;
 mod c_ffi_logger;
 mod c_ffi_output_attribute;
+mod c_ffi_panic;
 mod c_ffi_primitives;
 mod c_ffi_process_context;
 mod c_ffi_process_session;
@@ -11,14 +16,19 @@ mod c_ffi_processor_definition;
 mod c_ffi_processor_list;
 mod c_ffi_property;
 mod c_ffi_relationship;
+mod c_ffi_restored_flow_files;
 mod c_ffi_streams;
 
 pub use c_ffi_controller_service_definition::CffiControllerServiceDefinition;
 pub use c_ffi_controller_service_definition::DynRawControllerServiceDefinition;
 pub use c_ffi_controller_service_definition::RegisterableControllerService;
 pub use c_ffi_controller_service_list::CffiControllerServiceList;
+pub use c_ffi_extension_config::init_extension_config;
+pub use c_ffi_extension_state::{deinit_extension_state, init_extension_state};
+pub use c_ffi_flow_file::CffiFlowFile;
 pub use c_ffi_logger::CffiLogger;
 pub use c_ffi_primitives::StaticStrAsMinifiCStr;
+pub use c_ffi_process_session::CffiProcessSession;
 pub use c_ffi_processor_definition::DispatchOnTrigger;
 pub use c_ffi_processor_definition::DynRawProcessorDefinition;
 pub use c_ffi_processor_definition::RawProcessorDefinition;
@@ -1,20 +1,28 @@
+use crate::c_ffi::c_ffi_extension_config::extension_config;
+use crate::c_ffi::c_ffi_extension_state::extension_state;
 use crate::c_ffi::c_ffi_primitives::StringView;
-use crate::{GetProperty, MinifiError, Property};
+use crate::{
+    ExtensionState, GetConfig, GetExtensionState, GetInstanceMetadata, GetProperty,
+    InstanceMetadata, MinifiError, Property,
+};
 use minifi_native_sys::{
     MinifiControllerServiceContext, MinifiControllerServiceContextGetProperty,
     MinifiStatus_MINIFI_STATUS_SUCCESS, MinifiStringView,
 };
 use std::ffi::c_void;
+use std::sync::Arc;
 
 pub struct CffiControllerServiceContext<'a> {
     ptr: *mut MinifiControllerServiceContext,
+    metadata: InstanceMetadata,
     _lifetime: std::marker::PhantomData<&'a ()>,
 }
 
 impl<'a> CffiControllerServiceContext<'a> {
-    pub fn new(ptr: *mut MinifiControllerServiceContext) -> Self {
+    pub fn new(ptr: *mut MinifiControllerServiceContext, metadata: InstanceMetadata) -> Self {
         Self {
             ptr,
+            metadata,
             _lifetime: std::marker::PhantomData,
         }
     }
@@ -64,3 +72,21 @@ impl<'a> GetProperty for CffiControllerServiceContext<'a> {
         }
     }
 }
+
+impl<'a> GetConfig for CffiControllerServiceContext<'a> {
+    fn get_config(&self, key: &str) -> Result<Option<String>, MinifiError> {
+        extension_config().get_config(key)
+    }
+}
+
+impl<'a> GetExtensionState for CffiControllerServiceContext<'a> {
+    fn get_extension_state<S: ExtensionState>(&self) -> Option<Arc<S>> {
+        extension_state::<S>()
+    }
+}
+
+impl<'a> GetInstanceMetadata for CffiControllerServiceContext<'a> {
+    fn instance_metadata(&self) -> &InstanceMetadata {
+        &self.metadata
+    }
+}
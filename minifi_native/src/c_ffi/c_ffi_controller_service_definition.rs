@@ -1,14 +1,20 @@
 use crate::api::RawControllerService;
 use crate::c_ffi::c_ffi_controller_service_context::CffiControllerServiceContext;
+use crate::c_ffi::c_ffi_controller_service_lookup::{
+    register_controller_service, unregister_controller_service,
+};
+use crate::c_ffi::c_ffi_panic::contain_panic;
+use crate::c_ffi::c_ffi_primitives::instance_metadata;
 use crate::c_ffi::c_ffi_property::CProperties;
 use crate::c_ffi::{CffiLogger, StaticStrAsMinifiCStr};
 use crate::{
     ComponentIdentifier, ControllerService, ControllerServiceDefinition, EnableControllerService,
-    LogLevel, Property,
+    LogLevel, Logger, Phase, Property,
 };
 use minifi_native_sys::{
     MinifiControllerServiceCallbacks, MinifiControllerServiceClassDefinition,
     MinifiControllerServiceContext, MinifiControllerServiceMetadata, MinifiStatus,
+    MinifiStatus_MINIFI_STATUS_SUCCESS, MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
 };
 use std::ffi::c_void;
 
@@ -45,7 +51,7 @@ where
 
 impl<T> CffiControllerServiceDefinition<T>
 where
-    T: RawControllerService<LoggerType = CffiLogger> + ComponentIdentifier,
+    T: RawControllerService<LoggerType = CffiLogger> + ComponentIdentifier + 'static,
 {
     pub fn new(description_text: &'static str, properties: &'static [Property]) -> Self {
         let c_properties = Property::create_c_properties(properties);
@@ -58,18 +64,71 @@ where
         }
     }
 
+    /// Runs the callback unless the service is poisoned, a contained panic poisons it.
+    /// The fallback is returned in both cases
+    unsafe fn guarded<R>(
+        controller_service_ptr: *mut c_void,
+        callback_name: &str,
+        fallback: R,
+        callback: impl FnOnce() -> R,
+    ) -> R {
+        unsafe {
+            if (*(controller_service_ptr as *const T)).is_poisoned() {
+                return fallback;
+            }
+            contain_panic(callback).unwrap_or_else(|panic_message| {
+                let controller_service = &*(controller_service_ptr as *const T);
+                controller_service.poison();
+                controller_service.log(
+                    LogLevel::Critical,
+                    format_args!(
+                        "Panic during {callback_name} {panic_message}, the controller service is poisoned"
+                    ),
+                );
+                fallback
+            })
+        }
+    }
+
     unsafe extern "C" fn create_controller_service(
         metadata: MinifiControllerServiceMetadata,
     ) -> *mut c_void {
         let logger = CffiLogger::new(metadata.logger);
-        let controller_service = Box::new(T::new(logger));
-        Box::into_raw(controller_service) as *mut c_void
+        let controller_service = contain_panic(|| {
+            Box::new(T::new(
+                instance_metadata(&metadata.uuid, &metadata.name),
+                logger,
+            ))
+        });
+        match controller_service {
+            Ok(controller_service) => {
+                let controller_service_ptr = Box::into_raw(controller_service) as *mut c_void;
+                register_controller_service::<T>(controller_service_ptr);
+                controller_service_ptr
+            }
+            Err(panic_message) => {
+                logger.log(
+                    LogLevel::Critical,
+                    format_args!("Panic during create {panic_message}"),
+                );
+                std::ptr::null_mut()
+            }
+        }
     }
 
     unsafe extern "C" fn destroy_controller_service(controller_service_ptr: *mut c_void) {
         unsafe {
             if !controller_service_ptr.is_null() {
-                let _ = Box::from_raw(controller_service_ptr as *mut T);
+                unregister_controller_service(controller_service_ptr);
+                let logger = *(*(controller_service_ptr as *const T)).logger();
+                if let Err(panic_message) =
+                    contain_panic(|| drop(Box::from_raw(controller_service_ptr as *mut T)))
+                {
+                    logger.log(
+                        LogLevel::Critical,
+                        format_args!("Panic during destroy {panic_message}"),
+                    );
+                }
             }
         }
     }
@@ -79,22 +138,35 @@ where
         context_ptr: *mut MinifiControllerServiceContext,
     ) -> MinifiStatus {
         unsafe {
-            let controller_service = &mut *(controller_service_ptr as *mut T);
-            let context = CffiControllerServiceContext::new(context_ptr);
-            match controller_service.enable(&context) {
-                Ok(_) => 0,
-                Err(err) => {
-                    controller_service.log(LogLevel::Error, format_args!("{:?}", err));
-                    err.to_status()
-                }
-            }
+            Self::guarded(
+                controller_service_ptr,
+                "enable",
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+                || {
+                    let controller_service = &mut *(controller_service_ptr as *mut T);
+                    let context = CffiControllerServiceContext::new(
+                        context_ptr,
+                        controller_service.instance_metadata().clone(),
+                    );
+                    match controller_service.enable(&context) {
+                        Ok(_) => MinifiStatus_MINIFI_STATUS_SUCCESS,
+                        Err(minifi_error) => minifi_error.report_to_agent(
+                            controller_service.instance_metadata().name(),
+                            Phase::Enable,
+                            controller_service.logger(),
+                        ),
+                    }
+                },
+            )
         }
     }
 
     unsafe extern "C" fn disable_controller_service(controller_service_ptr: *mut c_void) {
         unsafe {
-            let controller_service = &mut *(controller_service_ptr as *mut T);
-            controller_service.disable()
+            Self::guarded(controller_service_ptr, "notifyStop", (), || {
+                let controller_service = &mut *(controller_service_ptr as *mut T);
+                controller_service.disable()
+            })
         }
     }
 }
@@ -105,7 +177,7 @@ pub trait DynRawControllerServiceDefinition {
 
 impl<T> DynRawControllerServiceDefinition for CffiControllerServiceDefinition<T>
 where
-    T: RawControllerService<LoggerType = CffiLogger> + ComponentIdentifier,
+    T: RawControllerService<LoggerType = CffiLogger> + ComponentIdentifier + 'static,
 {
     fn class_description(&'_ self) -> ControllerServiceClassDefinition<'_> {
         unsafe {
@@ -0,0 +1,186 @@
+use super::c_ffi_primitives::{ConvertMinifiStringView, FfiConversionError, StringView};
+use crate::api::RawControllerService;
+use crate::{ComponentIdentifier, InterfaceRequest, MinifiError};
+use minifi_native_sys::*;
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+
+type ProvideInterfaces = for<'a> unsafe fn(*const c_void, &mut InterfaceRequest<'a>);
+
+// Every controller service created by this extension, the agent can hand out services of other
+// extensions (or C++ ones) which must never be cast to a Rust type
+static CONTROLLER_SERVICES: Mutex<BTreeMap<usize, ProvideInterfaces>> = Mutex::new(BTreeMap::new());
+
+unsafe fn provide_interfaces<'a, T: RawControllerService + 'static>(
+    controller_service_ptr: *const c_void,
+    request: &mut InterfaceRequest<'a>,
+) {
+    unsafe {
+        let controller_service: &'a T = &*(controller_service_ptr as *const T);
+        controller_service.provide_interfaces(request);
+    }
+}
+
+pub(crate) fn register_controller_service<T: RawControllerService + 'static>(
+    controller_service_ptr: *const c_void,
+) {
+    if let Ok(mut controller_services) = CONTROLLER_SERVICES.lock() {
+        controller_services.insert(controller_service_ptr as usize, provide_interfaces::<T>);
+    }
+}
+
+pub(crate) fn unregister_controller_service(controller_service_ptr: *const c_void) {
+    if let Ok(mut controller_services) = CONTROLLER_SERVICES.lock() {
+        controller_services.remove(&(controller_service_ptr as usize));
+    }
+}
+
+fn registered_interface_provider(
+    controller_service_ptr: *const c_void,
+) -> Option<ProvideInterfaces> {
+    CONTROLLER_SERVICES
+        .lock()
+        .ok()?
+        .get(&(controller_service_ptr as usize))
+        .copied()
+}
+
+type ControllerServiceCallback = unsafe extern "C" fn(
+    *mut c_void,
+    *mut c_void,
+    MinifiStringView,
+    MinifiStringView,
+    MinifiStringView,
+) -> MinifiStatus;
+
+#[derive(Debug)]
+struct ControllerServiceHelper {
+    result: Option<*mut c_void>,
+    // None accepts any service, dyn lookups check the registry instead
+    identifier: Option<(&'static str, &'static str, &'static str)>,
+}
+
+impl ControllerServiceHelper {
+    fn is_valid(
+        &self,
+        class: &MinifiStringView,
+        grp: &MinifiStringView,
+        version: &MinifiStringView,
+    ) -> Result<bool, FfiConversionError> {
+        let Some((class_name_str, group_name_str, version_str)) = self.identifier else {
+            return Ok(true);
+        };
+        unsafe {
+            Ok(class_name_str.ends_with(class.as_str()?)
+                && group_name_str == grp.as_str()?
+                && version_str == version.as_str()?)
+        }
+    }
+}
+
+unsafe extern "C" fn get_controller_service_callback(
+    controller_service_helper_ptr: *mut c_void,
+    controller_ptr: *mut c_void,
+    class_name: MinifiStringView,
+    group_name: MinifiStringView,
+    version: MinifiStringView,
+) -> MinifiStatus {
+    unsafe {
+        let controller_service_helper =
+            &mut *(controller_service_helper_ptr as *mut ControllerServiceHelper);
+
+        match controller_service_helper.is_valid(&class_name, &group_name, &version) {
+            Ok(false) => MinifiStatus_MINIFI_STATUS_VALIDATION_FAILED,
+            Ok(true) => {
+                controller_service_helper.result = Some(controller_ptr);
+                MinifiStatus_MINIFI_STATUS_SUCCESS
+            }
+            Err(_e) => MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+        }
+    }
+}
+
+/// Resolves the controller service named service_name through one of the agent's lookup functions,
+/// the service is only accepted if its class, group and version match Cs
+pub(crate) unsafe fn lookup_controller_service<Cs, F>(
+    service_name: &str,
+    lookup_function_name: &str,
+    lookup: F,
+) -> Result<Option<*mut Cs>, MinifiError>
+where
+    Cs: ComponentIdentifier,
+    F: FnOnce(MinifiStringView, Option<ControllerServiceCallback>, *mut c_void) -> MinifiStatus,
+{
+    unsafe {
+        lookup_controller_service_ptr(
+            service_name,
+            lookup_function_name,
+            Some((Cs::CLASS_NAME, Cs::GROUP_NAME, Cs::VERSION)),
+            lookup,
+        )
+    }
+    .map(|result| result.map(|result| result as *mut Cs))
+}
+
+/// Resolves the controller service named service_name as the service interface I,
+/// only services created by this extension can be resolved this way
+pub(crate) unsafe fn lookup_controller_service_dyn<'a, I, F>(
+    service_name: &str,
+    lookup_function_name: &str,
+    lookup: F,
+) -> Result<Option<&'a I>, MinifiError>
+where
+    I: ?Sized + 'static,
+    F: FnOnce(MinifiStringView, Option<ControllerServiceCallback>, *mut c_void) -> MinifiStatus,
+{
+    let Some(controller_service_ptr) = (unsafe {
+        lookup_controller_service_ptr(service_name, lookup_function_name, None, lookup)?
+    }) else {
+        return Ok(None);
+    };
+    let Some(provide_interfaces) = registered_interface_provider(controller_service_ptr) else {
+        return Err(MinifiError::ControllerServiceError(
+            "The controller service is not provided by this extension",
+        ));
+    };
+    let mut request = InterfaceRequest::new::<I>();
+    unsafe { provide_interfaces(controller_service_ptr, &mut request) };
+    Ok(request.into_provided::<I>())
+}
+
+unsafe fn lookup_controller_service_ptr<F>(
+    service_name: &str,
+    lookup_function_name: &str,
+    identifier: Option<(&'static str, &'static str, &'static str)>,
+    lookup: F,
+) -> Result<Option<*mut c_void>, MinifiError>
+where
+    F: FnOnce(MinifiStringView, Option<ControllerServiceCallback>, *mut c_void) -> MinifiStatus,
+{
+    let class_name = identifier.map_or("dyn", |(class_name, _, _)| class_name);
+    let str_view = StringView::new(service_name);
+    let mut helper = ControllerServiceHelper {
+        result: None,
+        identifier,
+    };
+    unsafe {
+        let get_cs_status = lookup(
+            str_view.as_raw(),
+            Some(get_controller_service_callback),
+            &mut helper as *mut _ as *mut c_void,
+        );
+        if get_cs_status != MinifiStatus_MINIFI_STATUS_SUCCESS {
+            return Err(MinifiError::StatusError((
+                format!(
+                    "{}::<{:?}>({:?})",
+                    lookup_function_name, class_name, service_name
+                )
+                .into(),
+                NonZeroU32::new_unchecked(get_cs_status),
+            )));
+        }
+    }
+    Ok(helper.result)
+}
@@ -0,0 +1,19 @@
+use super::c_ffi_primitives::StaticStrAsMinifiCStr;
+use crate::DynamicProperty;
+use minifi_native_sys::MinifiDynamicPropertyDefinition;
+
+impl DynamicProperty {
+    pub(crate) fn create_c_vec(
+        dynamic_properties: &[Self],
+    ) -> Vec<MinifiDynamicPropertyDefinition> {
+        dynamic_properties
+            .iter()
+            .map(|p| MinifiDynamicPropertyDefinition {
+                name: p.name.as_minifi_c_type(),
+                value: p.value.as_minifi_c_type(),
+                description: p.description.as_minifi_c_type(),
+                supports_expression_language: p.supports_expr_lang,
+            })
+            .collect()
+    }
+}
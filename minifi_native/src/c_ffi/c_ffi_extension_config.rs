@@ -0,0 +1,44 @@
+use crate::api::extension_config::ExtensionConfig;
+use crate::c_ffi::c_ffi_primitives::{ConvertMinifiStringView, StringView};
+use minifi_native_sys::{MinifiConfig, MinifiConfigGet, MinifiStringView};
+use std::ffi::c_void;
+use std::sync::OnceLock;
+
+static EXTENSION_CONFIG: OnceLock<ExtensionConfig> = OnceLock::new();
+
+unsafe extern "C" fn config_value_callback(output_option: *mut c_void, value: MinifiStringView) {
+    unsafe {
+        let result_target = &mut *(output_option as *mut Option<String>);
+        *result_target = value.as_string().ok();
+    }
+}
+
+/// Reads the listed keys from the agent configuration, called by MinifiInitExtension
+///
+/// # Safety
+/// config must be null or the pointer received by MinifiInitExtension
+pub unsafe fn init_extension_config(config: *mut MinifiConfig, keys: &[&str]) {
+    let mut extension_config = ExtensionConfig::new();
+    if !config.is_null() {
+        for key in keys {
+            let mut value: Option<String> = None;
+            let config_key = StringView::new(key);
+            unsafe {
+                MinifiConfigGet(
+                    config,
+                    config_key.as_raw(),
+                    Some(config_value_callback),
+                    &mut value as *mut _ as *mut c_void,
+                );
+            }
+            if let Some(value) = value {
+                extension_config.insert(*key, value);
+            }
+        }
+    }
+    let _ = EXTENSION_CONFIG.set(extension_config);
+}
+
+pub(crate) fn extension_config() -> &'static ExtensionConfig {
+    EXTENSION_CONFIG.get_or_init(ExtensionConfig::new)
+}
@@ -0,0 +1,39 @@
+use crate::c_ffi::c_ffi_extension_config::extension_config;
+use crate::{ExtensionState, MinifiError};
+use std::any::Any;
+use std::ffi::c_void;
+use std::sync::{Arc, RwLock};
+
+static EXTENSION_STATE: RwLock<Option<Arc<dyn Any + Send + Sync>>> = RwLock::new(None);
+
+/// Creates the extension state, the returned pointer is the user_data of the extension
+///
+/// # Safety
+/// Must be called by MinifiInitExtension after init_extension_config
+pub unsafe fn init_extension_state<S: ExtensionState>() -> Result<*mut c_void, MinifiError> {
+    let state = Arc::new(S::init(extension_config())?);
+    if let Ok(mut extension_state) = EXTENSION_STATE.write() {
+        *extension_state = Some(state.clone());
+    }
+    Ok(Arc::into_raw(state) as *mut c_void)
+}
+
+/// The deinit callback of the extension
+///
+/// # Safety
+/// user_data must be the pointer returned by init_extension_state::<S>
+pub unsafe extern "C" fn deinit_extension_state<S: ExtensionState>(user_data: *mut c_void) {
+    if user_data.is_null() {
+        return;
+    }
+    let state = unsafe { Arc::from_raw(user_data as *const S) };
+    if let Ok(mut extension_state) = EXTENSION_STATE.write() {
+        extension_state.take();
+    }
+    state.deinit();
+}
+
+pub(crate) fn extension_state<S: ExtensionState>() -> Option<Arc<S>> {
+    let extension_state = EXTENSION_STATE.read().ok()?;
+    extension_state.clone()?.downcast::<S>().ok()
+}
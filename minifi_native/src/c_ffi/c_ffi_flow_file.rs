@@ -1,15 +1,26 @@
-use crate::api::FlowFile;
-use minifi_native_sys::MinifiFlowFile;
+use crate::MinifiError;
+use crate::api::{FlowFile, core_attributes};
+use crate::c_ffi::c_ffi_primitives::{ConvertMinifiStringView, StringView};
+use minifi_native_sys::{
+    MinifiFlowFile, MinifiFlowFileGetAttribute, MinifiInputStream, MinifiInputStreamSize,
+    MinifiProcessSession, MinifiProcessSessionRead, MinifiStatus_MINIFI_STATUS_SUCCESS,
+    MinifiStringView,
+};
+use std::ffi::c_void;
+use std::num::NonZeroU32;
 
 pub struct CffiFlowFile<'a> {
     ptr: *mut MinifiFlowFile,
+    // the agent only exposes attributes and content through the owning session
+    session_ptr: *mut MinifiProcessSession,
     _lifetime: std::marker::PhantomData<&'a ()>,
 }
 
 impl CffiFlowFile<'_> {
-    pub(crate) fn new(ptr: *mut MinifiFlowFile) -> Self {
+    pub fn new(ptr: *mut MinifiFlowFile, session_ptr: *mut MinifiProcessSession) -> Self {
         Self {
             ptr,
+            session_ptr,
             _lifetime: std::marker::PhantomData,
         }
     }
@@ -19,4 +30,72 @@ impl CffiFlowFile<'_> {
     }
 }
 
-impl FlowFile for CffiFlowFile<'_> {}
+pub(crate) unsafe fn get_flow_file_attribute(
+    session_ptr: *mut MinifiProcessSession,
+    flow_file_ptr: *mut MinifiFlowFile,
+    attr_key: &str,
+) -> Option<String> {
+    let mut attr_value: Option<String> = None;
+    unsafe {
+        unsafe extern "C" fn cb(rs_attr_value: *mut c_void, minifi_attr_value: MinifiStringView) {
+            unsafe {
+                let result_target = &mut *(rs_attr_value as *mut Option<String>);
+                *result_target = minifi_attr_value.as_string().ok()
+            }
+        }
+
+        let attr_key_string_view = StringView::new(attr_key);
+        MinifiFlowFileGetAttribute(
+            session_ptr,
+            flow_file_ptr,
+            attr_key_string_view.as_raw(),
+            Some(cb),
+            &mut attr_value as *mut _ as *mut c_void,
+        );
+    }
+    attr_value
+}
+
+impl FlowFile for CffiFlowFile<'_> {
+    fn attribute(&self, name: &str) -> Option<String> {
+        unsafe { get_flow_file_attribute(self.session_ptr, self.ptr, name) }
+    }
+
+    // The agent keeps the size in the fileSize attribute,
+    // the content is only opened (but not read) if that is missing
+    fn size(&self) -> Result<u64, MinifiError> {
+        if let Some(size) = self
+            .attribute(core_attributes::FILE_SIZE)
+            .and_then(|file_size| file_size.parse().ok())
+        {
+            return Ok(size);
+        }
+        let mut size: Option<u64> = None;
+        unsafe {
+            unsafe extern "C" fn cb(
+                size: *mut c_void,
+                input_stream: *mut MinifiInputStream,
+            ) -> i64 {
+                unsafe {
+                    let result_target = &mut *(size as *mut Option<u64>);
+                    *result_target = Some(MinifiInputStreamSize(input_stream) as u64);
+                    0
+                }
+            }
+
+            let status = MinifiProcessSessionRead(
+                self.session_ptr,
+                self.ptr,
+                Some(cb),
+                &mut size as *mut _ as *mut c_void,
+            );
+            if status != MinifiStatus_MINIFI_STATUS_SUCCESS {
+                return Err(MinifiError::StatusError((
+                    "MinifiProcessSessionRead".into(),
+                    NonZeroU32::new_unchecked(status),
+                )));
+            }
+        }
+        size.ok_or(MinifiError::UnknownError)
+    }
+}
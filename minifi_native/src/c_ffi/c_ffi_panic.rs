@@ -0,0 +1,69 @@
+/// Runs an agent callback, with the catch_unwind feature a panic is caught and returned as a message
+/// with its payload and location instead of aborting the agent.
+/// Catching requires an unwinding profile e.g. `cargo build --profile release-unwind --features minifi_native/catch_unwind`
+#[cfg(feature = "catch_unwind")]
+pub(crate) fn contain_panic<R>(callback: impl FnOnce() -> R) -> Result<R, String> {
+    location_hook::install();
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(callback)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        match location_hook::take_location() {
+            Some(location) => format!("'{message}' at {location}"),
+            None => format!("'{message}'"),
+        }
+    })
+}
+
+#[cfg(not(feature = "catch_unwind"))]
+#[inline(always)]
+pub(crate) fn contain_panic<R>(callback: impl FnOnce() -> R) -> Result<R, String> {
+    Ok(callback())
+}
+
+// the payload of a caught panic has no location, the panic hook records it for the panicking thread
+#[cfg(feature = "catch_unwind")]
+mod location_hook {
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    thread_local! {
+        static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    static INSTALL: Once = Once::new();
+
+    pub(super) fn install() {
+        INSTALL.call_once(|| {
+            let previous_hook = std::panic::take_hook();
+            std::panic::set_hook(Box::new(move |panic_info| {
+                let location = panic_info.location().map(|location| location.to_string());
+                PANIC_LOCATION.with_borrow_mut(|panic_location| *panic_location = location);
+                previous_hook(panic_info);
+            }));
+        });
+    }
+
+    pub(super) fn take_location() -> Option<String> {
+        PANIC_LOCATION.with_borrow_mut(Option::take)
+    }
+}
+
+#[cfg(all(test, feature = "catch_unwind"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panic_is_contained_with_payload_and_location() {
+        assert_eq!(contain_panic(|| 5), Ok(5));
+
+        let panic_message = contain_panic(|| panic!("kamikaze {}", 42)).unwrap_err();
+        assert!(panic_message.starts_with("'kamikaze 42' at "));
+        assert!(panic_message.contains(file!()));
+
+        let panic_message = contain_panic(|| std::panic::panic_any(7)).unwrap_err();
+        assert!(panic_message.starts_with("'Box<dyn Any>'"));
+    }
+}
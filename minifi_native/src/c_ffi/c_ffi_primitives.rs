@@ -1,4 +1,4 @@
-use crate::ProcessorInputRequirement;
+use crate::{InstanceMetadata, ProcessorInputRequirement};
 use minifi_native_sys::{
     MinifiInputRequirement, MinifiInputRequirement_MINIFI_INPUT_ALLOWED,
     MinifiInputRequirement_MINIFI_INPUT_FORBIDDEN, MinifiInputRequirement_MINIFI_INPUT_REQUIRED,
@@ -74,6 +74,19 @@ impl ConvertMinifiStringView for MinifiStringView {
     }
 }
 
+/// The agent always sets the uuid and the name, an unreadable one is left empty
+pub(crate) fn instance_metadata(
+    uuid: &MinifiStringView,
+    name: &MinifiStringView,
+) -> InstanceMetadata {
+    unsafe {
+        InstanceMetadata::new(
+            uuid.as_str().unwrap_or_default(),
+            name.as_str().unwrap_or_default(),
+        )
+    }
+}
+
 impl ProcessorInputRequirement {
     pub fn as_minifi_c_type(&self) -> MinifiInputRequirement {
         match self {
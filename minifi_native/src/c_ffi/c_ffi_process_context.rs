@@ -1,26 +1,59 @@
+use super::c_ffi_controller_service_lookup::{
+    lookup_controller_service, lookup_controller_service_dyn,
+};
 use super::c_ffi_flow_file::CffiFlowFile;
-use super::c_ffi_primitives::{ConvertMinifiStringView, FfiConversionError, StringView};
+use super::c_ffi_primitives::StringView;
 use crate::api::controller_service::ControllerService;
 use crate::api::{ProcessContext, RawControllerService};
 use crate::c_ffi::CffiLogger;
-use crate::{ComponentIdentifier, EnableControllerService, MinifiError, Property};
+use crate::c_ffi::c_ffi_extension_config::extension_config;
+use crate::c_ffi::c_ffi_extension_state::extension_state;
+use crate::{
+    ComponentIdentifier, EnableControllerService, ExtensionState, GetConfig, GetExtensionState,
+    GetInstanceMetadata, InstanceMetadata, MinifiError, Property,
+};
 use minifi_native_sys::*;
 use std::ffi::c_void;
 use std::num::NonZeroU32;
+use std::sync::Arc;
 
 /// A safe wrapper around a `MinifiProcessContext` pointer.
 pub struct CffiProcessContext<'a> {
     ptr: *mut MinifiProcessContext,
+    metadata: InstanceMetadata,
     _lifetime: std::marker::PhantomData<&'a ()>,
 }
 
 impl<'a> CffiProcessContext<'a> {
-    pub fn new(ptr: *mut MinifiProcessContext) -> Self {
+    pub fn new(ptr: *mut MinifiProcessContext, metadata: InstanceMetadata) -> Self {
         Self {
             ptr,
+            metadata,
             _lifetime: std::marker::PhantomData,
         }
     }
+
+    fn get_property_by_name(
+        &self,
+        name: &str,
+        flow_file: Option<&CffiFlowFile<'a>>,
+    ) -> (MinifiStatus, Option<String>) {
+        let ff_ptr = flow_file.map_or(std::ptr::null_mut(), |ff| ff.get_ptr());
+
+        let mut result: Option<String> = None;
+        let property_name: StringView = StringView::new(name);
+
+        let status = unsafe {
+            MinifiProcessContextGetProperty(
+                self.ptr,
+                property_name.as_raw(),
+                ff_ptr,
+                Some(get_property_callback),
+                &mut result as *mut _ as *mut c_void,
+            )
+        };
+        (status, result)
+    }
 }
 
 unsafe extern "C" fn get_property_callback(
@@ -43,48 +76,21 @@ unsafe extern "C" fn get_property_callback(
     }
 }
 
-#[derive(Debug)]
-struct ControllerServiceHelper {
-    result: Option<*mut c_void>,
-    class_name_str: &'static str,
-    group_name_str: &'static str,
-    version_str: &'static str,
+impl<'a> GetConfig for CffiProcessContext<'a> {
+    fn get_config(&self, key: &str) -> Result<Option<String>, MinifiError> {
+        extension_config().get_config(key)
+    }
 }
 
-impl ControllerServiceHelper {
-    fn is_valid(
-        &self,
-        class: &MinifiStringView,
-        grp: &MinifiStringView,
-        version: &MinifiStringView,
-    ) -> Result<bool, FfiConversionError> {
-        unsafe {
-            Ok(self.class_name_str.ends_with(class.as_str()?)
-                && self.group_name_str == grp.as_str()?
-                && self.version_str == version.as_str()?)
-        }
+impl<'a> GetExtensionState for CffiProcessContext<'a> {
+    fn get_extension_state<S: ExtensionState>(&self) -> Option<Arc<S>> {
+        extension_state::<S>()
     }
 }
 
-unsafe extern "C" fn get_controller_service_callback(
-    controller_service_helper_ptr: *mut c_void,
-    controller_ptr: *mut c_void,
-    class_name: MinifiStringView,
-    group_name: MinifiStringView,
-    version: MinifiStringView,
-) -> MinifiStatus {
-    unsafe {
-        let controller_service_helper =
-            &mut *(controller_service_helper_ptr as *mut ControllerServiceHelper);
-
-        match controller_service_helper.is_valid(&class_name, &group_name, &version) {
-            Ok(false) => MinifiStatus_MINIFI_STATUS_VALIDATION_FAILED,
-            Ok(true) => {
-                controller_service_helper.result = Some(controller_ptr);
-                MinifiStatus_MINIFI_STATUS_SUCCESS
-            }
-            Err(_e) => MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
-        }
+impl<'a> GetInstanceMetadata for CffiProcessContext<'a> {
+    fn instance_metadata(&self) -> &InstanceMetadata {
+        &self.metadata
     }
 }
 
@@ -95,30 +101,33 @@ impl<'a> ProcessContext for CffiProcessContext<'a> {
         property: &Property,
         flow_file: Option<&Self::FlowFile>,
     ) -> Result<Option<String>, MinifiError> {
-        let ff_ptr = flow_file.map_or(std::ptr::null_mut(), |ff| ff.get_ptr());
-
-        let mut result: Option<String> = None;
-        let property_name: StringView = StringView::new(property.name);
+        #[allow(non_upper_case_globals)]
+        match self.get_property_by_name(property.name, flow_file) {
+            (MinifiStatus_MINIFI_STATUS_SUCCESS, result) => Ok(result),
+            (MinifiStatus_MINIFI_STATUS_PROPERTY_NOT_SET, _) => match property.is_required {
+                true => Err(MinifiError::MissingRequiredProperty(property.name)),
+                false => Ok(None),
+            },
+            (err_code, _) => Err(MinifiError::StatusError((
+                format!("MinifiProcessContextGetProperty({:?})", property.name).into(),
+                unsafe { NonZeroU32::new_unchecked(err_code) },
+            ))),
+        }
+    }
 
+    fn get_dynamic_property(
+        &self,
+        name: &str,
+        flow_file: Option<&Self::FlowFile>,
+    ) -> Result<Option<String>, MinifiError> {
         #[allow(non_upper_case_globals)]
-        unsafe {
-            match MinifiProcessContextGetProperty(
-                self.ptr,
-                property_name.as_raw(),
-                ff_ptr,
-                Some(get_property_callback),
-                &mut result as *mut _ as *mut c_void,
-            ) {
-                MinifiStatus_MINIFI_STATUS_SUCCESS => Ok(result),
-                MinifiStatus_MINIFI_STATUS_PROPERTY_NOT_SET => match property.is_required {
-                    true => Err(MinifiError::MissingRequiredProperty(property.name)),
-                    false => Ok(None),
-                },
-                err_code => Err(MinifiError::StatusError((
-                    format!("MinifiProcessContextGetProperty({:?})", property.name).into(),
-                    NonZeroU32::new_unchecked(err_code),
-                ))),
-            }
+        match self.get_property_by_name(name, flow_file) {
+            (MinifiStatus_MINIFI_STATUS_SUCCESS, result) => Ok(result),
+            (MinifiStatus_MINIFI_STATUS_PROPERTY_NOT_SET, _) => Ok(None),
+            (err_code, _) => Err(MinifiError::StatusError((
+                format!("MinifiProcessContextGetProperty({:?})", name).into(),
+                unsafe { NonZeroU32::new_unchecked(err_code) },
+            ))),
         }
     }
 
@@ -130,43 +139,19 @@ impl<'a> ProcessContext for CffiProcessContext<'a> {
         Cs: RawControllerService + ComponentIdentifier + 'static,
     {
         if let Some(service_name) = self.get_property(property, None)? {
-            let str_view = StringView::new(service_name.as_str());
-            let mut helper = ControllerServiceHelper {
-                result: None,
-                class_name_str: Cs::CLASS_NAME,
-                group_name_str: Cs::GROUP_NAME,
-                version_str: Cs::VERSION,
-            };
             unsafe {
-                let get_cs_status = MinifiProcessContextGetControllerService(
-                    self.ptr,
-                    str_view.as_raw(),
-                    Some(get_controller_service_callback),
-                    &mut helper as *mut _ as *mut c_void,
-                );
-                if get_cs_status != MinifiStatus_MINIFI_STATUS_SUCCESS {
-                    return Err(MinifiError::StatusError((
-                        format!(
-                            "MinifiProcessContextGetControllerService::<{:?}>({:?})",
-                            Cs::CLASS_NAME,
-                            service_name
-                        )
-                        .into(),
-                        NonZeroU32::new_unchecked(get_cs_status),
-                    )));
-                }
-            }
-
-            match helper.result {
-                Some(result) => {
-                    let foo_ref: &Cs = unsafe {
-                        (result as *const Cs)
-                            .as_ref()
-                            .expect("C returned a null pointer")
-                    };
-                    Ok(Some(foo_ref))
-                }
-                None => Ok(None),
+                let service = lookup_controller_service::<Cs, _>(
+                    &service_name,
+                    "MinifiProcessContextGetControllerService",
+                    |name, cb, user_ctx| {
+                        MinifiProcessContextGetControllerService(self.ptr, name, cb, user_ctx)
+                    },
+                )?;
+                Ok(service.map(|service| {
+                    (service as *const Cs)
+                        .as_ref()
+                        .expect("C returned a null pointer")
+                }))
             }
         } else {
             Ok(None)
@@ -182,4 +167,22 @@ impl<'a> ProcessContext for CffiProcessContext<'a> {
             Some(f) => Ok(f.get_implementation()),
         }
     }
+
+    fn get_controller_service_dyn<I>(&self, property: &Property) -> Result<Option<&I>, MinifiError>
+    where
+        I: ?Sized + 'static,
+    {
+        let Some(service_name) = self.get_property(property, None)? else {
+            return Ok(None);
+        };
+        unsafe {
+            lookup_controller_service_dyn::<I, _>(
+                &service_name,
+                "MinifiProcessContextGetControllerService",
+                |name, cb, user_ctx| {
+                    MinifiProcessContextGetControllerService(self.ptr, name, cb, user_ctx)
+                },
+            )
+        }
+    }
 }
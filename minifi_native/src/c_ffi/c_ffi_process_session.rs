@@ -1,17 +1,16 @@
-use super::c_ffi_flow_file::CffiFlowFile;
+use super::c_ffi_flow_file::{CffiFlowFile, get_flow_file_attribute};
 use crate::MinifiError;
 use crate::api::process_session::{IoState, OutputStream};
 use crate::api::{InputStream, ProcessSession};
 use crate::c_ffi::c_ffi_primitives::{ConvertMinifiStringView, StringView};
 use crate::c_ffi::c_ffi_streams::{CffiInputStream, CffiOutputStream};
 use minifi_native_sys::{
-    MinifiFlowFileGetAttribute, MinifiFlowFileGetAttributes, MinifiFlowFileSetAttribute,
-    MinifiInputStream, MinifiInputStreamRead, MinifiInputStreamSize,
-    MinifiIoStatus_MINIFI_IO_CANCEL, MinifiIoStatus_MINIFI_IO_ERROR, MinifiOutputStream,
-    MinifiOutputStreamWrite, MinifiProcessSession, MinifiProcessSessionCreate,
-    MinifiProcessSessionGet, MinifiProcessSessionRead, MinifiProcessSessionRemove,
-    MinifiProcessSessionTransfer, MinifiProcessSessionWrite, MinifiStatus_MINIFI_STATUS_SUCCESS,
-    MinifiStringView,
+    MinifiFlowFileGetAttributes, MinifiFlowFileSetAttribute, MinifiInputStream,
+    MinifiInputStreamRead, MinifiInputStreamSize, MinifiIoStatus_MINIFI_IO_CANCEL,
+    MinifiIoStatus_MINIFI_IO_ERROR, MinifiOutputStream, MinifiOutputStreamWrite,
+    MinifiProcessSession, MinifiProcessSessionCreate, MinifiProcessSessionGet,
+    MinifiProcessSessionRead, MinifiProcessSessionRemove, MinifiProcessSessionTransfer,
+    MinifiProcessSessionWrite, MinifiStatus_MINIFI_STATUS_SUCCESS, MinifiStringView,
 };
 use std::ffi::{CString, c_void};
 use std::io::Read;
@@ -108,7 +107,16 @@ impl<'a> ProcessSession for CffiProcessSession<'a> {
         if ff_ptr.is_null() {
             Err(MinifiError::UnknownError)
         } else {
-            Ok(CffiFlowFile::new(ff_ptr))
+            Ok(CffiFlowFile::new(ff_ptr, self.ptr))
+        }
+    }
+
+    fn create_child(&mut self, parent: &Self::FlowFile) -> Result<Self::FlowFile, MinifiError> {
+        let ff_ptr = unsafe { MinifiProcessSessionCreate(self.ptr, parent.get_ptr()) };
+        if ff_ptr.is_null() {
+            Err(MinifiError::UnknownError)
+        } else {
+            Ok(CffiFlowFile::new(ff_ptr, self.ptr))
         }
     }
 
@@ -117,7 +125,7 @@ impl<'a> ProcessSession for CffiProcessSession<'a> {
         if ff_ptr.is_null() {
             None
         } else {
-            Some(CffiFlowFile::new(ff_ptr))
+            Some(CffiFlowFile::new(ff_ptr, self.ptr))
         }
     }
 
@@ -180,29 +188,32 @@ impl<'a> ProcessSession for CffiProcessSession<'a> {
         }
     }
 
-    fn get_attribute(&self, flow_file: &Self::FlowFile, attr_key: &str) -> Option<String> {
-        let mut attr_value: Option<String> = None;
+    fn remove_attribute(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        attr_key: &str,
+    ) -> Result<(), MinifiError> {
         unsafe {
-            unsafe extern "C" fn cb(
-                rs_attr_value: *mut c_void,
-                minifi_attr_value: MinifiStringView,
-            ) {
-                unsafe {
-                    let result_target = &mut *(rs_attr_value as *mut Option<String>);
-                    *result_target = minifi_attr_value.as_string().ok()
-                }
-            }
-
             let attr_key_string_view = StringView::new(attr_key);
-            MinifiFlowFileGetAttribute(
+            // a null value removes the attribute
+            match MinifiFlowFileSetAttribute(
                 self.ptr,
                 flow_file.get_ptr(),
                 attr_key_string_view.as_raw(),
-                Some(cb),
-                &mut attr_value as *mut _ as *mut c_void,
-            );
+                std::ptr::null(),
+            ) {
+                #[allow(non_upper_case_globals)]
+                MinifiStatus_MINIFI_STATUS_SUCCESS => Ok(()),
+                err_code => Err(MinifiError::StatusError((
+                    format!("MinifiFlowFileSetAttribute({}, null)", attr_key).into(),
+                    NonZeroU32::new_unchecked(err_code),
+                ))),
+            }
         }
-        attr_value
+    }
+
+    fn get_attribute(&self, flow_file: &Self::FlowFile, attr_key: &str) -> Option<String> {
+        unsafe { get_flow_file_attribute(self.ptr, flow_file.get_ptr(), attr_key) }
     }
 
     fn on_attributes<F: FnMut(&str, &str)>(
@@ -249,23 +260,48 @@ impl<'a> ProcessSession for CffiProcessSession<'a> {
     }
 
     fn write(&self, flow_file: &Self::FlowFile, data: &[u8]) -> Result<(), MinifiError> {
-        let mut dt: Option<&[u8]> = Some(data);
+        self.write_vectored(flow_file, &[data], 0)
+    }
+
+    fn write_vectored(
+        &self,
+        flow_file: &Self::FlowFile,
+        slices: &[&[u8]],
+        chunk_size: usize,
+    ) -> Result<(), MinifiError> {
+        struct VectoredWrite<'s, 'd> {
+            slices: &'s [&'d [u8]],
+            chunk_size: usize,
+        }
+
+        let mut vectored_write = VectoredWrite { slices, chunk_size };
         unsafe {
             unsafe extern "C" fn cb(
                 user_ctx: *mut c_void,
                 output_stream: *mut MinifiOutputStream,
             ) -> i64 {
                 unsafe {
-                    let result_target = &mut *(user_ctx as *mut Option<&str>);
-                    if result_target.is_none() {
-                        return MinifiIoStatus_MINIFI_IO_ERROR;
+                    let vectored_write = &*(user_ctx as *const VectoredWrite);
+                    let mut overall_writes = 0;
+                    for slice in vectored_write.slices {
+                        let chunk_size = match vectored_write.chunk_size {
+                            0 => slice.len().max(1),
+                            chunk_size => chunk_size,
+                        };
+                        // the chunks point into the caller's slices, nothing is copied on our side
+                        for chunk in slice.chunks(chunk_size) {
+                            let written = MinifiOutputStreamWrite(
+                                output_stream,
+                                chunk.as_ptr() as *const c_char,
+                                chunk.len(),
+                            );
+                            if written < 0 {
+                                return MinifiIoStatus_MINIFI_IO_ERROR;
+                            }
+                            overall_writes += written;
+                        }
                     }
-
-                    MinifiOutputStreamWrite(
-                        output_stream,
-                        result_target.unwrap().as_ptr() as *const c_char,
-                        result_target.unwrap().len(),
-                    )
+                    overall_writes
                 }
             }
 
@@ -273,7 +309,7 @@ impl<'a> ProcessSession for CffiProcessSession<'a> {
                 self.ptr,
                 flow_file.get_ptr(),
                 Some(cb),
-                &mut dt as *mut _ as *mut c_void,
+                &mut vectored_write as *mut _ as *mut c_void,
             ) {
                 #[allow(non_upper_case_globals)]
                 MinifiStatus_MINIFI_STATUS_SUCCESS => Ok(()),
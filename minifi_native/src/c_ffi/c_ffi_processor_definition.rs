@@ -1,22 +1,54 @@
 use std::ffi::c_void;
-use std::ptr;
 
-use super::c_ffi_primitives::{StaticStrAsMinifiCStr, StringView};
+use super::c_ffi_flow_file::CffiFlowFile;
+use super::c_ffi_panic::contain_panic;
+use super::c_ffi_primitives::{StaticStrAsMinifiCStr, StringView, instance_metadata};
 use super::c_ffi_process_context::CffiProcessContext;
 use super::c_ffi_process_session::CffiProcessSession;
+use super::c_ffi_restored_flow_files::{add_restored_flow_file, take_restored_flow_files};
+use crate::api::component_definition_traits::relationship_is_listed;
+use crate::api::processor_metrics::measured_on_trigger;
 use crate::api::raw_processor::{MultiThreadedTrigger, SingleThreadedTrigger};
 use crate::api::{ProcessorInputRequirement, RawProcessor, ThreadingModel};
 use crate::c_ffi::CffiLogger;
 use crate::c_ffi::c_ffi_output_attribute::COutputAttributes;
 use crate::c_ffi::c_ffi_property::CProperties;
 use crate::{
-    AdvancedProcessorFeatures, CalculateMetrics, ComponentIdentifier, Concurrent, Exclusive,
-    LogLevel, OutputAttribute, Processor, ProcessorDefinition, Property, Schedule,
+    AdvancedProcessorFeatures, CalculateMetrics, ComponentIdentifier, Concurrent, DynamicProperty,
+    Exclusive, LogLevel, Logger, OutputAttribute, Phase, ProcessSession, Processor,
+    ProcessorDefinition, Property, Schedule,
 };
 use crate::{OnTriggerResult, Relationship};
 use minifi_native_sys::*;
 
+/// The C API can't add a restored flow file to a session, so a processor can't take it over.
+/// Its owned handle is removed in the next session of the processor instead of being leaked
+fn remove_restored_flow_files<L: Logger>(
+    processor_ptr: *const c_void,
+    session: &mut CffiProcessSession,
+    session_ptr: *mut MinifiProcessSession,
+    logger: &L,
+) {
+    for flow_file_ptr in take_restored_flow_files(processor_ptr) {
+        let result = session.remove(CffiFlowFile::new(flow_file_ptr, session_ptr));
+        match result {
+            Ok(()) => logger.log(
+                LogLevel::Warn,
+                format_args!(
+                    "Restoring flow files is not supported, the restored flow file is removed"
+                ),
+            ),
+            Err(err) => logger.log(
+                LogLevel::Warn,
+                format_args!("Failed to remove a restored flow file: {err}"),
+            ),
+        }
+    }
+}
+
 pub trait DispatchOnTrigger<M: ThreadingModel> {
+    const FAILURE_RELATIONSHIP: Option<&'static str>;
+
     unsafe fn dispatch_on_trigger(
         processor: *mut c_void,
         context: *mut MinifiProcessContext,
@@ -28,6 +60,9 @@ impl<T> DispatchOnTrigger<Concurrent> for T
 where
     T: MultiThreadedTrigger,
 {
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        <T as MultiThreadedTrigger>::FAILURE_RELATIONSHIP;
+
     unsafe fn dispatch_on_trigger(
         processor_ptr: *mut c_void,
         context_ptr: *mut MinifiProcessContext,
@@ -35,18 +70,27 @@ where
     ) -> MinifiStatus {
         unsafe {
             let processor = &*(processor_ptr as *const T);
-            let mut context = CffiProcessContext::new(context_ptr);
+            let mut context =
+                CffiProcessContext::new(context_ptr, processor.instance_metadata().clone());
             let mut session = CffiProcessSession::new(session_ptr);
-            match processor.on_trigger(&mut context, &mut session) {
+            remove_restored_flow_files(
+                processor_ptr,
+                &mut session,
+                session_ptr,
+                processor.logger(),
+            );
+            let result =
+                measured_on_trigger(processor.processor_metrics(), &mut session, |session| {
+                    processor.on_trigger(&mut context, session)
+                });
+            match result {
                 Ok(OnTriggerResult::Ok) => MinifiStatus_MINIFI_STATUS_SUCCESS,
                 Ok(OnTriggerResult::Yield) => MinifiStatus_MINIFI_STATUS_PROCESSOR_YIELD,
-                Err(minifi_error) => {
-                    processor.log(
-                        LogLevel::Error,
-                        format_args!("Error during on_trigger {}", minifi_error),
-                    );
-                    minifi_error.to_status()
-                }
+                Err(minifi_error) => minifi_error.report_to_agent(
+                    processor.instance_metadata().name(),
+                    Phase::Trigger,
+                    processor.logger(),
+                ),
             }
         }
     }
@@ -56,6 +100,9 @@ impl<T> DispatchOnTrigger<Exclusive> for T
 where
     T: SingleThreadedTrigger,
 {
+    const FAILURE_RELATIONSHIP: Option<&'static str> =
+        <T as SingleThreadedTrigger>::FAILURE_RELATIONSHIP;
+
     unsafe fn dispatch_on_trigger(
         processor_ptr: *mut c_void,
         context_ptr: *mut MinifiProcessContext,
@@ -63,12 +110,27 @@ where
     ) -> MinifiStatus {
         unsafe {
             let processor = &mut *(processor_ptr as *mut T);
-            let mut context = CffiProcessContext::new(context_ptr);
+            let mut context =
+                CffiProcessContext::new(context_ptr, processor.instance_metadata().clone());
             let mut session = CffiProcessSession::new(session_ptr);
-            match processor.on_trigger(&mut context, &mut session) {
+            remove_restored_flow_files(
+                processor_ptr,
+                &mut session,
+                session_ptr,
+                processor.logger(),
+            );
+            let metrics = processor.processor_metrics().clone();
+            let result = measured_on_trigger(&metrics, &mut session, |session| {
+                processor.on_trigger(&mut context, session)
+            });
+            match result {
                 Ok(OnTriggerResult::Ok) => MinifiStatus_MINIFI_STATUS_SUCCESS,
                 Ok(OnTriggerResult::Yield) => MinifiStatus_MINIFI_STATUS_PROCESSOR_YIELD,
-                Err(error_code) => error_code.to_status(),
+                Err(minifi_error) => minifi_error.report_to_agent(
+                    processor.instance_metadata().name(),
+                    Phase::Trigger,
+                    processor.logger(),
+                ),
             }
         }
     }
@@ -87,6 +149,7 @@ where
     c_output_attributes: COutputAttributes,
     c_relationships: Vec<MinifiRelationshipDefinition>,
     c_properties: CProperties,
+    c_dynamic_properties: Vec<MinifiDynamicPropertyDefinition>,
 
     _phantom: std::marker::PhantomData<T>,
 }
@@ -104,9 +167,11 @@ where
         output_attributes: &'static [OutputAttribute],
         relationships: &'static [Relationship],
         properties: &'static [Property],
+        dynamic_properties: &'static [DynamicProperty],
     ) -> Self {
         let c_relationships = Relationship::create_c_vec(relationships);
         let c_properties = Property::create_c_properties(properties);
+        let c_dynamic_properties = DynamicProperty::create_c_vec(dynamic_properties);
         let c_output_attributes = COutputAttributes::new(output_attributes);
 
         Self {
@@ -118,20 +183,71 @@ where
             c_output_attributes,
             c_relationships,
             c_properties,
+            c_dynamic_properties,
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Runs the callback unless the processor is poisoned, a contained panic poisons it.
+    /// The fallback is returned in both cases
+    unsafe fn guarded<R>(
+        processor_ptr: *mut c_void,
+        callback_name: &str,
+        fallback: R,
+        callback: impl FnOnce() -> R,
+    ) -> R {
+        unsafe {
+            if (*(processor_ptr as *const T)).is_poisoned() {
+                return fallback;
+            }
+            contain_panic(callback).unwrap_or_else(|panic_message| {
+                let processor = &*(processor_ptr as *const T);
+                processor.poison();
+                processor.log(
+                    LogLevel::Critical,
+                    format_args!(
+                        "Panic during {callback_name} {panic_message}, the processor is poisoned"
+                    ),
+                );
+                fallback
+            })
+        }
+    }
+
     unsafe extern "C" fn create_processor(metadata: MinifiProcessorMetadata) -> *mut c_void {
         let logger = super::c_ffi_logger::CffiLogger::new(metadata.logger);
-        let processor = Box::new(T::new(logger));
-        Box::into_raw(processor) as *mut c_void
+        let processor = contain_panic(|| {
+            Box::new(T::new(
+                instance_metadata(&metadata.uuid, &metadata.name),
+                logger,
+            ))
+        });
+        match processor {
+            Ok(processor) => Box::into_raw(processor) as *mut c_void,
+            Err(panic_message) => {
+                logger.log(
+                    LogLevel::Critical,
+                    format_args!("Panic during create {panic_message}"),
+                );
+                std::ptr::null_mut()
+            }
+        }
     }
 
     unsafe extern "C" fn destroy_processor(processor_ptr: *mut c_void) {
         unsafe {
             if !processor_ptr.is_null() {
-                let _ = Box::from_raw(processor_ptr as *mut T);
+                // without a session there is nothing left to do with the restored handles
+                drop(take_restored_flow_files(processor_ptr));
+                let logger = *(*(processor_ptr as *const T)).logger();
+                if let Err(panic_message) =
+                    contain_panic(|| drop(Box::from_raw(processor_ptr as *mut T)))
+                {
+                    logger.log(
+                        LogLevel::Critical,
+                        format_args!("Panic during destroy {panic_message}"),
+                    );
+                }
             }
         }
     }
@@ -142,10 +258,17 @@ where
         session_ptr: *mut MinifiProcessSession,
     ) -> MinifiStatus {
         unsafe {
-            <T as DispatchOnTrigger<T::Threading>>::dispatch_on_trigger(
+            Self::guarded(
                 processor_ptr,
-                context_ptr,
-                session_ptr,
+                "onTrigger",
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+                || {
+                    <T as DispatchOnTrigger<T::Threading>>::dispatch_on_trigger(
+                        processor_ptr,
+                        context_ptr,
+                        session_ptr,
+                    )
+                },
             )
         }
     }
@@ -155,43 +278,58 @@ where
         context_ptr: *mut MinifiProcessContext,
     ) -> MinifiStatus {
         unsafe {
-            let processor = &mut *(processor_ptr as *mut T);
-            let context = CffiProcessContext::new(context_ptr);
-            match processor.on_schedule(&context) {
-                Ok(_) => 0,
-                Err(error_code) => {
-                    processor.log(
-                        LogLevel::Error,
-                        format_args!("Error during on_schedule: {}", error_code),
-                    );
-                    error_code.to_status()
-                }
-            }
+            Self::guarded(
+                processor_ptr,
+                "onSchedule",
+                MinifiStatus_MINIFI_STATUS_UNKNOWN_ERROR,
+                || {
+                    let processor = &mut *(processor_ptr as *mut T);
+                    let context =
+                        CffiProcessContext::new(context_ptr, processor.instance_metadata().clone());
+                    match processor.on_schedule(&context) {
+                        Ok(_) => MinifiStatus_MINIFI_STATUS_SUCCESS,
+                        Err(minifi_error) => minifi_error.report_to_agent(
+                            processor.instance_metadata().name(),
+                            Phase::Schedule,
+                            processor.logger(),
+                        ),
+                    }
+                },
+            )
         }
     }
 
     unsafe extern "C" fn on_unschedule_processor(processor_ptr: *mut c_void) {
         unsafe {
-            let processor = &mut *(processor_ptr as *mut T);
-            processor.on_unschedule();
+            Self::guarded(processor_ptr, "onUnSchedule", (), || {
+                let processor = &mut *(processor_ptr as *mut T);
+                processor.on_unschedule();
+            })
         }
     }
 
     unsafe extern "C" fn is_work_available(processor_ptr: *mut c_void) -> bool {
         unsafe {
-            let processor = &*(processor_ptr as *const T);
-            processor.is_work_available()
+            Self::guarded(processor_ptr, "isWorkAvailable", false, || {
+                let processor = &*(processor_ptr as *const T);
+                processor.is_work_available()
+            })
         }
     }
 
-    unsafe extern "C" fn restore(_processor_ptr: *mut c_void, _flow_file: *mut MinifiFlowFile) {
-        eprintln!("Restore is not implemented for this processor.");
+    // Can be called while the processor is triggered, so the processor is not accessed here
+    unsafe extern "C" fn restore(processor_ptr: *mut c_void, flow_file_ptr: *mut MinifiFlowFile) {
+        if !flow_file_ptr.is_null() {
+            add_restored_flow_file(processor_ptr, flow_file_ptr);
+        }
     }
 
     unsafe extern "C" fn get_trigger_when_empty(processor_ptr: *mut c_void) -> bool {
         unsafe {
-            let processor = &*(processor_ptr as *const T);
-            processor.get_trigger_when_empty()
+            Self::guarded(processor_ptr, "getTriggerWhenEmpty", false, || {
+                let processor = &*(processor_ptr as *const T);
+                processor.get_trigger_when_empty()
+            })
         }
     }
 
@@ -199,8 +337,10 @@ where
         processor_ptr: *mut c_void,
     ) -> *mut MinifiPublishedMetrics {
         unsafe {
-            let processor = &*(processor_ptr as *const T);
-            let metrics = processor.calculate_metrics();
+            let metrics = Self::guarded(processor_ptr, "calculateMetrics", vec![], || {
+                let processor = &*(processor_ptr as *const T);
+                processor.calculate_metrics()
+            });
             let metric_values: Vec<f64> = metrics.iter().map(|(_k, v)| *v).collect();
 
             let metric_string_view: Vec<StringView> = metrics
@@ -255,8 +395,8 @@ where
                 description: self.description_text.as_minifi_c_type(),
                 class_properties_count: self.c_properties.len(),
                 class_properties_ptr: self.c_properties.get_ptr(),
-                dynamic_properties_count: 0,
-                dynamic_properties_ptr: ptr::null(),
+                dynamic_properties_count: self.c_dynamic_properties.len(),
+                dynamic_properties_ptr: self.c_dynamic_properties.as_ptr(),
                 class_relationships_count: self.c_relationships.len(),
                 class_relationships_ptr: self.c_relationships.as_ptr(),
                 output_attributes_count: self.c_output_attributes.len(),
@@ -282,6 +422,10 @@ where
 }
 
 pub trait RawRegisterableProcessor {
+    /// Fails to evaluate if on_trigger routes to a relationship that isn't one of the processor's,
+    /// get_definition evaluates it so registering such a processor doesn't compile
+    const RELATIONSHIPS_CHECK: ();
+
     fn get_definition() -> Box<dyn DynRawProcessorDefinition>;
 }
 
@@ -298,7 +442,19 @@ where
     Processor<Implementation, Kind, Threading, CffiLogger>:
         RawProcessor<Threading = Threading, LoggerType = CffiLogger> + DispatchOnTrigger<Threading>,
 {
+    const RELATIONSHIPS_CHECK: () = {
+        if let Some(failure_relationship) =
+            <Self as DispatchOnTrigger<Threading>>::FAILURE_RELATIONSHIP
+        {
+            assert!(
+                relationship_is_listed(failure_relationship, Implementation::RELATIONSHIPS),
+                "The failure relationship of the processor is not one of its RELATIONSHIPS"
+            );
+        }
+    };
+
     fn get_definition() -> Box<dyn DynRawProcessorDefinition> {
+        let () = Self::RELATIONSHIPS_CHECK;
         Box::new(RawProcessorDefinition::<
             Processor<Implementation, Kind, Threading, CffiLogger>,
         >::new(
@@ -310,6 +466,7 @@ where
             Implementation::OUTPUT_ATTRIBUTES,
             Implementation::RELATIONSHIPS,
             Implementation::PROPERTIES,
+            Implementation::DYNAMIC_PROPERTIES,
         ))
     }
 }
@@ -0,0 +1,32 @@
+use minifi_native_sys::MinifiFlowFile;
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::sync::Mutex;
+
+// Flow files the agent restored to a processor, by processor. The restore callback can come while
+// the processor is triggered, so it only records the handle here instead of touching the processor
+static RESTORED_FLOW_FILES: Mutex<BTreeMap<usize, Vec<usize>>> = Mutex::new(BTreeMap::new());
+
+pub(crate) fn add_restored_flow_file(
+    processor_ptr: *const c_void,
+    flow_file_ptr: *mut MinifiFlowFile,
+) {
+    if let Ok(mut restored_flow_files) = RESTORED_FLOW_FILES.lock() {
+        restored_flow_files
+            .entry(processor_ptr as usize)
+            .or_default()
+            .push(flow_file_ptr as usize);
+    }
+}
+
+pub(crate) fn take_restored_flow_files(processor_ptr: *const c_void) -> Vec<*mut MinifiFlowFile> {
+    let Ok(mut restored_flow_files) = RESTORED_FLOW_FILES.lock() else {
+        return Vec::new();
+    };
+    restored_flow_files
+        .remove(&(processor_ptr as usize))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|flow_file_ptr| flow_file_ptr as *mut MinifiFlowFile)
+        .collect()
+}
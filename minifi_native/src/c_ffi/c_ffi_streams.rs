@@ -1,5 +1,8 @@
+use crate::api::InputStream;
+use crate::api::input_stream::SeekableInputStream;
 use minifi_native_sys::{
-    MinifiInputStream, MinifiInputStreamRead, MinifiOutputStream, MinifiOutputStreamWrite,
+    MinifiInputStream, MinifiInputStreamRead, MinifiInputStreamSize, MinifiOutputStream,
+    MinifiOutputStreamWrite,
 };
 use std::io::{BufRead, Error, ErrorKind, Read};
 
@@ -70,6 +73,17 @@ impl<'a> BufRead for CffiInputStream<'a> {
     }
 }
 
+// the C API reads sequentially only, seeking falls back to buffering
+impl<'a> InputStream for CffiInputStream<'a> {
+    fn size(&self) -> Option<u64> {
+        unsafe { Some(MinifiInputStreamSize(self.ptr) as u64) }
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        None
+    }
+}
+
 #[derive(Debug)]
 pub struct CffiOutputStream<'a> {
     ptr: *mut MinifiOutputStream,
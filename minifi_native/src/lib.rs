@@ -2,16 +2,28 @@ mod api;
 pub mod c_ffi;
 pub mod mock;
 
-pub use api::errors::MinifiError;
+pub use api::errors::{ErrorContext, ErrorReport, MinifiError, Phase};
 
 pub use api::component_definition_traits::{
     ComponentIdentifier, ControllerServiceDefinition, ProcessorDefinition,
+    output_attributes_match_relationships,
 };
 pub use api::controller_service::{ControllerService, EnableControllerService};
+pub use api::extension_config::{ExtensionConfig, GetConfig};
+pub use api::extension_state::{ExtensionState, GetExtensionState};
+pub use api::instance_metadata::{GetInstanceMetadata, InstanceMetadata};
 pub use api::processor_wrappers::complex_processor::{ComplexProcessorType, MutTrigger, Trigger};
+pub use api::processor_wrappers::flow_file_aggregator::{
+    BIN_ENTRY_TIME_ATTRIBUTE, BinEntry, BinLimits, FlowFileAggregator,
+    FlowFileAggregatorProcessorType, MergedFlowFile,
+};
 pub use api::processor_wrappers::flow_file_source::{
     FlowFileSource, FlowFileSourceProcessorType, GeneratedFlowFile,
 };
+pub use api::processor_wrappers::flow_file_split_transform::{
+    FlowFileSplitTransform, FlowFileSplitTransformProcessorType, MutFlowFileSplitTransform,
+    SplitFlowFile, SplitTransformResult,
+};
 pub use api::processor_wrappers::flow_file_stream_transform::{
     FlowFileStreamTransform, FlowFileStreamTransformProcessorType, MutFlowFileStreamTransform,
     TransformStreamResult,
@@ -19,24 +31,34 @@ pub use api::processor_wrappers::flow_file_stream_transform::{
 pub use api::processor_wrappers::flow_file_transform::{
     FlowFileTransform, FlowFileTransformProcessorType, TransformedFlowFile,
 };
+pub use api::service_interface::InterfaceRequest;
 
 pub use api::processor_wrappers::utils::flow_file_content::Content;
+pub use api::processor_wrappers::utils::transform_batch::TransformBatch;
 
 pub use api::processor::{AdvancedProcessorFeatures, CalculateMetrics, Processor, Schedule};
+pub use api::processor_metrics::StandardMetrics;
 
 pub use api::raw_processor::{Concurrent, Exclusive};
 
 pub use api::logger::{LogLevel, Logger};
 
-pub use api::property::{GetControllerService, GetProperty, Property};
+pub use api::metrics::{Counter, Gauge, Histogram, Metric, Metrics};
+
+pub use api::property::{
+    DataSize, DynamicProperty, EnumPropertyValue, GetControllerService, GetProperty, Properties,
+    Property, PropertyValue,
+};
 
+pub use api::input_stream::{SeekableInputStream, SeekableStream};
 pub use api::process_session::IoState;
 
 pub use api::attribute::{GetAttribute, OutputAttribute};
 
 pub use api::{
-    FlowFile, InputStream, OnTriggerResult, OutputStream, ProcessContext, ProcessSession,
-    ProcessorInputRequirement, Relationship, StandardPropertyValidator,
+    DynamicRelationship, FlowFile, InputStream, OnTriggerResult, OutputStream, ProcessContext,
+    ProcessSession, ProcessorInputRequirement, Relationship, StandardPropertyValidator,
+    core_attributes,
 };
 
 pub use minifi_native_macros as macros;
@@ -54,24 +76,63 @@ pub use mock::{
 pub static MinifiApiVersion: u32 = minifi_native_sys::MINIFI_API_VERSION;
 
 /// Defines the required MinifiInitExtension C function to register the listed processors and controller services
+///
+/// The optional config_keys are read from minifi.properties during the init,
+/// processors and controller services can access them through GetConfig.
+/// The optional state is created after the config is read, they can access it through GetExtensionState.
 #[macro_export]
 macro_rules! declare_minifi_extension {
     (
+        // Match the ExtensionState type
+        $( state: $state:ty, )?
         // Match a tuple of three types for each processor
         processors: [ $( ($impl:ty, $kind:ty, $thread:ty) ),* $(,)? ],
         // Match a single type for each controller service
         controllers: [ $( $ctrl:ty ),* $(,)? ]
+        // Match the minifi.properties keys used by the extension
+        $(, config_keys: [ $( $config_key:expr ),* $(,)? ] )?
+        $(,)?
     ) => {
 
         #[unsafe(no_mangle)]
         #[allow(non_snake_case)]
+        #[allow(clippy::not_unsafe_ptr_arg_deref)] // the pointers come from the agent
         pub extern "C" fn MinifiInitExtension(
             extension: *mut minifi_native::sys::MinifiExtension,
-            _config: *mut minifi_native::sys::MinifiConfig,
+            config: *mut minifi_native::sys::MinifiConfig,
         ) {
 
             use minifi_native::c_ffi::StaticStrAsMinifiCStr;
             unsafe {
+                minifi_native::c_ffi::init_extension_config(
+                    config,
+                    &[ $( $( $config_key ),* )? ],
+                );
+
+                #[allow(unused_mut)]
+                let mut deinit = None;
+                #[allow(unused_mut)]
+                let mut user_data = std::ptr::null_mut();
+                $(
+                    // the agent has no logger for the extension before it is created, so the error goes to stderr,
+                    // returning without MinifiCreateExtension makes the agent report it as not loaded
+                    user_data = match minifi_native::c_ffi::init_extension_state::<$state>() {
+                        Ok(state) => state,
+                        Err(error) => {
+                            eprintln!(
+                                "Failed to initialize the state of the {} extension: {}",
+                                env!("CARGO_PKG_NAME"),
+                                error.report()
+                            );
+                            return;
+                        }
+                    };
+                    deinit = Some(
+                        minifi_native::c_ffi::deinit_extension_state::<$state>
+                            as unsafe extern "C" fn(*mut std::ffi::c_void),
+                    );
+                )?
+
                 let mut processor_list = minifi_native::c_ffi::CffiProcessorList::new();
 
                 $(
@@ -103,8 +164,8 @@ macro_rules! declare_minifi_extension {
                 let extension_create_info = minifi_native::sys::MinifiExtensionCreateInfo {
                     name: env!("CARGO_PKG_NAME").as_minifi_c_type(),
                     version: env!("CARGO_PKG_VERSION").as_minifi_c_type(),
-                    deinit: None,
-                    user_data: std::ptr::null_mut(),
+                    deinit,
+                    user_data,
                     processors_count: processor_list.get_processor_count(),
                     processors_ptr: processor_list.get_processor_ptr(),
                     controller_services_count: controller_list.get_controller_service_count(),
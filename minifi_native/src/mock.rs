@@ -3,10 +3,12 @@ mod mock_flow_file;
 mod mock_logger;
 mod mock_process_context;
 mod mock_process_session;
+#[cfg(test)]
+pub(crate) mod test_processor;
 
 pub use mock_controller_service_context::MockControllerServiceContext;
 pub use mock_flow_file::MockFlowFile;
 pub use mock_logger::MockLogger;
 pub use mock_logger::StdLogger;
-pub use mock_process_context::MockProcessContext;
+pub use mock_process_context::{MockControllerServices, MockProcessContext};
 pub use mock_process_session::MockProcessSession;
@@ -1,8 +1,18 @@
+use crate::api::RawControllerService;
 use crate::mock::mock_process_context::MockPropertyMap;
-use crate::{GetProperty, MinifiError, Property};
+use crate::{
+    ComponentIdentifier, ControllerService, EnableControllerService, ExtensionConfig,
+    ExtensionState, GetConfig, GetExtensionState, GetInstanceMetadata, GetProperty,
+    InstanceMetadata, Logger, MinifiError, Property,
+};
+use std::any::Any;
+use std::sync::Arc;
 
 pub struct MockControllerServiceContext {
     pub properties: MockPropertyMap,
+    pub config: ExtensionConfig,
+    pub extension_state: Option<Arc<dyn Any + Send + Sync>>,
+    pub metadata: InstanceMetadata,
 }
 
 impl GetProperty for MockControllerServiceContext {
@@ -11,10 +21,103 @@ impl GetProperty for MockControllerServiceContext {
     }
 }
 
+impl GetConfig for MockControllerServiceContext {
+    fn get_config(&self, key: &str) -> Result<Option<String>, MinifiError> {
+        self.config.get_config(key)
+    }
+}
+
+impl GetExtensionState for MockControllerServiceContext {
+    fn get_extension_state<S: ExtensionState>(&self) -> Option<Arc<S>> {
+        self.extension_state.clone()?.downcast::<S>().ok()
+    }
+}
+
+impl GetInstanceMetadata for MockControllerServiceContext {
+    fn instance_metadata(&self) -> &InstanceMetadata {
+        &self.metadata
+    }
+}
+
 impl MockControllerServiceContext {
     pub fn new() -> Self {
         Self {
             properties: MockPropertyMap::new(),
+            config: ExtensionConfig::new(),
+            extension_state: None,
+            metadata: InstanceMetadata::default(),
         }
     }
+
+    /// Creates and enables the controller service the same way the agent does
+    pub fn enable_controller_service<Cs, L>(
+        &self,
+        logger: L,
+    ) -> Result<ControllerService<Cs, L>, MinifiError>
+    where
+        Cs: EnableControllerService + ComponentIdentifier,
+        L: Logger,
+    {
+        let mut controller_service = ControllerService::new(self.metadata.clone(), logger);
+        controller_service.enable(self)?;
+        Ok(controller_service)
+    }
+
+    /// Disables the controller service the same way the agent does when it stops the service
+    pub fn disable_controller_service<Cs, L>(
+        &self,
+        controller_service: &mut ControllerService<Cs, L>,
+    ) where
+        Cs: EnableControllerService + ComponentIdentifier,
+        L: Logger,
+    {
+        controller_service.disable();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockLogger;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static DISABLED: AtomicBool = AtomicBool::new(false);
+
+    struct ConnectionPool;
+
+    impl ComponentIdentifier for ConnectionPool {
+        const CLASS_NAME: &'static str = "ConnectionPool";
+        const GROUP_NAME: &'static str = "rust_extension";
+        const VERSION: &'static str = "0.0.1";
+    }
+
+    impl EnableControllerService for ConnectionPool {
+        fn enable<
+            Ctx: GetProperty + GetConfig + GetExtensionState + GetInstanceMetadata,
+            L: Logger,
+        >(
+            _context: &Ctx,
+            _logger: &L,
+        ) -> Result<Self, MinifiError> {
+            Ok(ConnectionPool)
+        }
+
+        fn disable(&mut self) {
+            DISABLED.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_disable_drops_the_implementation() {
+        let context = MockControllerServiceContext::new();
+        let mut controller_service = context
+            .enable_controller_service::<ConnectionPool, _>(MockLogger::new())
+            .unwrap();
+        assert!(controller_service.is_enabled());
+
+        context.disable_controller_service(&mut controller_service);
+        assert!(DISABLED.load(Ordering::SeqCst));
+        assert!(!controller_service.is_enabled());
+        assert!(controller_service.get_implementation().is_none());
+    }
 }
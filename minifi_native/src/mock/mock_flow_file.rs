@@ -1,29 +1,79 @@
-use crate::api::FlowFile;
+use crate::MinifiError;
+use crate::api::{FlowFile, core_attributes};
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+static NEXT_MOCK_FLOW_FILE_ID: AtomicU64 = AtomicU64::new(1);
 
 pub struct MockFlowFile {
+    pub id: u64,
+    pub parent_id: Option<u64>,
+    pub children_ids: RefCell<Vec<u64>>,
     pub content: RefCell<Vec<u8>>,
     pub attributes: HashMap<String, String>,
+    pub entry_date: SystemTime,
+    pub lineage_start_date: SystemTime,
 }
 
-impl FlowFile for MockFlowFile {}
+impl FlowFile for MockFlowFile {
+    fn attribute(&self, name: &str) -> Option<String> {
+        self.attributes.get(name).cloned()
+    }
+
+    /// The uuid attribute if the test set one, otherwise one derived from the id
+    fn uuid(&self) -> Option<String> {
+        self.attribute(core_attributes::UUID)
+            .or_else(|| Some(format!("00000000-0000-0000-0000-{:012x}", self.id)))
+    }
+
+    fn entry_date(&self) -> Option<SystemTime> {
+        Some(self.entry_date)
+    }
+
+    fn lineage_start_date(&self) -> Option<SystemTime> {
+        Some(self.lineage_start_date)
+    }
+
+    fn size(&self) -> Result<u64, MinifiError> {
+        Ok(self.content_len() as u64)
+    }
+}
 
 impl MockFlowFile {
     pub fn new() -> MockFlowFile {
-        MockFlowFile {
-            content: RefCell::new(Vec::new()),
-            attributes: HashMap::new(),
-        }
+        Self::with_content(&[])
     }
 
     pub fn with_content(content: &[u8]) -> MockFlowFile {
+        let now = SystemTime::now();
         Self {
+            id: NEXT_MOCK_FLOW_FILE_ID.fetch_add(1, Ordering::Relaxed),
+            parent_id: None,
+            children_ids: RefCell::new(Vec::new()),
             content: RefCell::new(content.to_vec()),
             attributes: HashMap::new(),
+            entry_date: now,
+            lineage_start_date: now,
         }
     }
 
+    /// Creates a child that inherits the attributes (but not the content) of self
+    pub fn new_child(&self) -> MockFlowFile {
+        let mut child = Self::new();
+        child.parent_id = Some(self.id);
+        child.attributes = self.attributes.clone();
+        child.attributes.remove(core_attributes::UUID);
+        child.lineage_start_date = self.lineage_start_date;
+        self.children_ids.borrow_mut().push(child.id);
+        child
+    }
+
+    pub fn is_child_of(&self, other: &MockFlowFile) -> bool {
+        self.parent_id == Some(other.id)
+    }
+
     pub fn content_len(&self) -> usize {
         self.content.borrow().len()
     }
@@ -40,3 +90,44 @@ impl MockFlowFile {
         std::io::Cursor::new(self.content.borrow().clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn core_attributes() {
+        let mut flow_file = MockFlowFile::with_content(b"hello");
+        flow_file.attributes.insert(
+            core_attributes::FILENAME.to_string(),
+            "hello.txt".to_string(),
+        );
+        flow_file
+            .attributes
+            .insert(core_attributes::PATH.to_string(), "in/".to_string());
+
+        assert_eq!(flow_file.filename().as_deref(), Some("hello.txt"));
+        assert_eq!(flow_file.path().as_deref(), Some("in/"));
+        assert_eq!(flow_file.absolute_path(), None);
+        assert_eq!(flow_file.mime_type(), None);
+        assert_eq!(flow_file.size().unwrap(), 5);
+        assert_eq!(flow_file.entry_date(), flow_file.lineage_start_date());
+    }
+
+    #[test]
+    fn children_have_their_own_uuid_but_share_the_lineage() {
+        let mut parent = MockFlowFile::new();
+        parent.lineage_start_date -= Duration::from_secs(60);
+        parent
+            .attributes
+            .insert(core_attributes::UUID.to_string(), "parent-uuid".to_string());
+        let child = parent.new_child();
+
+        assert_eq!(parent.uuid().as_deref(), Some("parent-uuid"));
+        assert!(child.uuid().is_some());
+        assert_ne!(child.uuid(), parent.uuid());
+        assert_eq!(child.lineage_start_date, parent.lineage_start_date);
+        assert!(child.entry_date > child.lineage_start_date);
+    }
+}
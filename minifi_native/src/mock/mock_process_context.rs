@@ -1,9 +1,12 @@
 use crate::api::{ProcessContext, RawControllerService};
 use crate::{
-    ComponentIdentifier, EnableControllerService, GetAttribute, MinifiError, MockFlowFile, Property,
+    ComponentIdentifier, EnableControllerService, ExtensionConfig, ExtensionState, GetAttribute,
+    GetConfig, GetExtensionState, GetInstanceMetadata, InstanceMetadata, InterfaceRequest,
+    MinifiError, MockFlowFile, Property,
 };
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 pub struct MockPropertyMap {
     pub properties: HashMap<String, String>,
@@ -55,10 +58,61 @@ impl MockPropertyMap {
     }
 }
 
+type ProvideInterfaces = for<'a> fn(&'a dyn Any, &mut InterfaceRequest<'a>);
+
+fn provide_interfaces<'a, Cs: EnableControllerService + 'static>(
+    controller_service: &'a dyn Any,
+    request: &mut InterfaceRequest<'a>,
+) {
+    if let Some(controller_service) = controller_service.downcast_ref::<Cs>() {
+        controller_service.provide_interfaces(request);
+    }
+}
+
+/// Enabled controller services by name, they can be looked up by type or by service interface
+#[derive(Default)]
+pub struct MockControllerServices {
+    controller_services: HashMap<String, (Box<dyn Any>, ProvideInterfaces)>,
+}
+
+impl MockControllerServices {
+    pub fn new() -> Self {
+        Self {
+            controller_services: HashMap::new(),
+        }
+    }
+
+    pub fn insert<K, Cs>(&mut self, name: K, controller_service: Box<Cs>)
+    where
+        K: Into<String>,
+        Cs: EnableControllerService + 'static,
+    {
+        self.controller_services
+            .insert(name.into(), (controller_service, provide_interfaces::<Cs>));
+    }
+
+    pub fn get<Cs: 'static>(&self, name: &str) -> Option<&Cs> {
+        self.controller_services
+            .get(name)
+            .and_then(|(controller_service, _)| controller_service.downcast_ref::<Cs>())
+    }
+
+    pub fn get_dyn<I: ?Sized + 'static>(&self, name: &str) -> Option<&I> {
+        let (controller_service, provide_interfaces) = self.controller_services.get(name)?;
+        let mut request = InterfaceRequest::new::<I>();
+        provide_interfaces(controller_service.as_ref(), &mut request);
+        request.into_provided::<I>()
+    }
+}
+
 pub struct MockProcessContext {
     pub properties: MockPropertyMap,
-    pub controller_services: HashMap<String, Box<dyn Any>>,
+    pub dynamic_properties: MockPropertyMap,
+    pub controller_services: MockControllerServices,
     pub attributes: HashMap<String, String>,
+    pub config: ExtensionConfig,
+    pub extension_state: Option<Arc<dyn Any + Send + Sync>>,
+    pub metadata: InstanceMetadata,
 }
 
 impl ProcessContext for MockProcessContext {
@@ -72,6 +126,14 @@ impl ProcessContext for MockProcessContext {
         self.properties.get_property(property, _flow_file)
     }
 
+    fn get_dynamic_property(
+        &self,
+        name: &str,
+        _flow_file: Option<&Self::FlowFile>,
+    ) -> Result<Option<String>, MinifiError> {
+        Ok(self.dynamic_properties.properties.get(name).cloned())
+    }
+
     fn get_raw_controller_service<Cs>(
         &self,
         _property: &Property,
@@ -87,10 +149,18 @@ impl ProcessContext for MockProcessContext {
         Cs: EnableControllerService + ComponentIdentifier + 'static,
     {
         if let Some(service_name) = self.get_property(property, None)? {
-            Ok(self
-                .controller_services
-                .get(&service_name)
-                .and_then(|c| c.downcast_ref::<Cs>()))
+            Ok(self.controller_services.get::<Cs>(&service_name))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn get_controller_service_dyn<I>(&self, property: &Property) -> Result<Option<&I>, MinifiError>
+    where
+        I: ?Sized + 'static,
+    {
+        if let Some(service_name) = self.get_property(property, None)? {
+            Ok(self.controller_services.get_dyn::<I>(&service_name))
         } else {
             Ok(None)
         }
@@ -101,14 +171,176 @@ impl MockProcessContext {
     pub fn new() -> Self {
         Self {
             properties: MockPropertyMap::new(),
-            controller_services: HashMap::new(),
+            dynamic_properties: MockPropertyMap::new(),
+            controller_services: MockControllerServices::new(),
             attributes: HashMap::new(),
+            config: ExtensionConfig::new(),
+            extension_state: None,
+            metadata: InstanceMetadata::default(),
         }
     }
 }
 
+impl GetConfig for MockProcessContext {
+    fn get_config(&self, key: &str) -> Result<Option<String>, MinifiError> {
+        self.config.get_config(key)
+    }
+}
+
+impl GetExtensionState for MockProcessContext {
+    fn get_extension_state<S: ExtensionState>(&self) -> Option<Arc<S>> {
+        self.extension_state.clone()?.downcast::<S>().ok()
+    }
+}
+
+impl GetInstanceMetadata for MockProcessContext {
+    fn instance_metadata(&self) -> &InstanceMetadata {
+        &self.metadata
+    }
+}
+
 impl GetAttribute for MockProcessContext {
     fn get_attribute(&self, name: &str) -> Result<Option<String>, MinifiError> {
         Ok(self.attributes.get(name).cloned())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_properties() {
+        let mut context = MockProcessContext::new();
+        context
+            .dynamic_properties
+            .insert("foo".to_string(), "bar".to_string());
+        assert_eq!(
+            context.get_dynamic_property("foo", None).unwrap(),
+            Some("bar".to_string())
+        );
+        assert_eq!(context.get_dynamic_property("missing", None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_extension_config() {
+        let mut context = MockProcessContext::new();
+        context.config.insert("nifi.rs.batch.size", "100");
+
+        assert_eq!(
+            context
+                .get_typed_config::<u64>("nifi.rs.batch.size")
+                .unwrap(),
+            Some(100)
+        );
+        assert_eq!(context.get_config("nifi.rs.missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_extension_state() {
+        struct Counter(u32);
+        impl ExtensionState for Counter {
+            fn init(_config: &ExtensionConfig) -> Result<Self, MinifiError> {
+                Ok(Counter(42))
+            }
+        }
+        struct Other;
+        impl ExtensionState for Other {
+            fn init(_config: &ExtensionConfig) -> Result<Self, MinifiError> {
+                Ok(Other)
+            }
+        }
+
+        let mut context = MockProcessContext::new();
+        assert!(context.get_extension_state::<Counter>().is_none());
+
+        context.extension_state = Some(Arc::new(Counter::init(&context.config).unwrap()));
+        assert_eq!(context.get_extension_state::<Counter>().unwrap().0, 42);
+        assert!(context.get_extension_state::<Other>().is_none());
+    }
+
+    #[test]
+    fn test_instance_metadata() {
+        let mut context = MockProcessContext::new();
+        context.metadata =
+            InstanceMetadata::new("4a3b2c1d-0000-1111-2222-333344445555", "My Processor");
+
+        assert_eq!(
+            context.instance_metadata().uuid(),
+            "4a3b2c1d-0000-1111-2222-333344445555"
+        );
+        assert_eq!(context.instance_metadata().name(), "My Processor");
+    }
+
+    #[test]
+    fn test_controller_service_by_interface() {
+        trait KeyProvider {
+            fn key(&self) -> &str;
+        }
+
+        struct EnvKeyProvider;
+
+        impl ComponentIdentifier for EnvKeyProvider {
+            const CLASS_NAME: &'static str = "EnvKeyProvider";
+            const GROUP_NAME: &'static str = "rust_extension";
+            const VERSION: &'static str = "0.0.1";
+        }
+
+        impl KeyProvider for EnvKeyProvider {
+            fn key(&self) -> &str {
+                "secret"
+            }
+        }
+
+        impl EnableControllerService for EnvKeyProvider {
+            fn enable<Ctx: crate::GetProperty, L: crate::Logger>(
+                _context: &Ctx,
+                _logger: &L,
+            ) -> Result<Self, MinifiError> {
+                Ok(EnvKeyProvider)
+            }
+
+            fn provide_interfaces<'a>(&'a self, request: &mut InterfaceRequest<'a>) {
+                request.provide::<dyn KeyProvider>(self);
+            }
+        }
+
+        trait Unrelated {}
+
+        const KEY_PROVIDER: Property = Property {
+            name: "Key Provider",
+            description: "Any key provider service",
+            is_required: true,
+            is_sensitive: false,
+            supports_expr_lang: false,
+            default_value: None,
+            validator: crate::StandardPropertyValidator::AlwaysValidValidator,
+            allowed_values: &[],
+            allowed_type: "KeyProvider",
+        };
+
+        let mut context = MockProcessContext::new();
+        context.properties.insert("Key Provider", "env_keys");
+        context
+            .controller_services
+            .insert("env_keys", Box::new(EnvKeyProvider));
+
+        let key_provider = context
+            .get_controller_service_dyn::<dyn KeyProvider>(&KEY_PROVIDER)
+            .unwrap()
+            .unwrap();
+        assert_eq!(key_provider.key(), "secret");
+        assert!(
+            context
+                .get_controller_service_dyn::<dyn Unrelated>(&KEY_PROVIDER)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            context
+                .get_controller_service::<EnvKeyProvider>(&KEY_PROVIDER)
+                .unwrap()
+                .is_some()
+        );
+    }
+}
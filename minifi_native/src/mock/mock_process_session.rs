@@ -1,9 +1,9 @@
 use crate::api::process_session::IoState;
 use crate::api::{InputStream, ProcessSession};
-use crate::{MinifiError, MockFlowFile};
+use crate::{MinifiError, MockFlowFile, SeekableInputStream};
 use itertools::Itertools;
 use std::cell::RefCell;
-use std::io::Read;
+use std::io::{BufRead, Read, Seek, SeekFrom};
 
 pub struct TransferredFlowFile {
     pub relationship: String,
@@ -13,6 +13,50 @@ pub struct TransferredFlowFile {
 pub struct MockProcessSession {
     pub input_flow_files: Vec<MockFlowFile>,
     pub transferred_flow_files: RefCell<Vec<TransferredFlowFile>>,
+    /// Whether read_stream provides native random access, like a content repository that supports seeking
+    pub seekable_content: bool,
+}
+
+/// The content read through read_stream, seekable only if the session says so
+#[derive(Debug)]
+struct MockInputStream {
+    content: std::io::Cursor<Vec<u8>>,
+    seekable: bool,
+}
+
+impl Read for MockInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.content.read(buf)
+    }
+}
+
+impl BufRead for MockInputStream {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.content.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.content.consume(amount);
+    }
+}
+
+impl Seek for MockInputStream {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        if !self.seekable {
+            return Err(std::io::ErrorKind::Unsupported.into());
+        }
+        self.content.seek(pos)
+    }
+}
+
+impl InputStream for MockInputStream {
+    fn size(&self) -> Option<u64> {
+        self.content.size()
+    }
+
+    fn as_seekable(&mut self) -> Option<&mut dyn SeekableInputStream> {
+        if self.seekable { Some(self) } else { None }
+    }
 }
 
 impl ProcessSession for MockProcessSession {
@@ -21,6 +65,17 @@ impl ProcessSession for MockProcessSession {
     fn create(&mut self) -> Result<Self::FlowFile, MinifiError> {
         Ok(Self::FlowFile::new())
     }
+    fn create_child(&mut self, parent: &Self::FlowFile) -> Result<Self::FlowFile, MinifiError> {
+        Ok(parent.new_child())
+    }
+    fn clone_flow_file(
+        &mut self,
+        flow_file: &Self::FlowFile,
+    ) -> Result<Self::FlowFile, MinifiError> {
+        let clone = flow_file.new_child();
+        *clone.content.borrow_mut() = flow_file.content.borrow().clone();
+        Ok(clone)
+    }
     fn get(&mut self) -> Option<Self::FlowFile> {
         self.input_flow_files.pop()
     }
@@ -49,6 +104,15 @@ impl ProcessSession for MockProcessSession {
             .insert(attr_key.to_string(), attr_value.to_string());
         Ok(())
     }
+    fn remove_attribute(
+        &self,
+        flow_file: &mut Self::FlowFile,
+        attr_key: &str,
+    ) -> Result<(), MinifiError> {
+        flow_file.attributes.remove(attr_key);
+        Ok(())
+    }
+
     fn get_attribute(&self, flow_file: &Self::FlowFile, attr_key: &str) -> Option<String> {
         flow_file.attributes.get(attr_key).cloned()
     }
@@ -75,33 +139,78 @@ impl ProcessSession for MockProcessSession {
         flow_file: &Self::FlowFile,
         mut stream: Box<dyn Read + 'a>,
     ) -> Result<(), MinifiError> {
+        let mut content = flow_file.content.borrow_mut();
+        content.clear();
         stream
-            .read_to_end(&mut flow_file.content.borrow_mut())
+            .read_to_end(&mut content)
             .expect("Mock data should be readable");
         Ok(())
     }
 
+    fn write_vectored(
+        &self,
+        flow_file: &Self::FlowFile,
+        slices: &[&[u8]],
+        chunk_size: usize,
+    ) -> Result<(), MinifiError> {
+        let mut content = flow_file.content.borrow_mut();
+        content.clear();
+        for slice in slices {
+            for chunk in slice.chunks(if chunk_size == 0 {
+                slice.len().max(1)
+            } else {
+                chunk_size
+            }) {
+                content.extend_from_slice(chunk);
+            }
+        }
+        Ok(())
+    }
+
     fn write_stream<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
+    where
+        F: FnOnce(
+            &mut dyn crate::api::process_session::OutputStream,
+        ) -> Result<(R, IoState), MinifiError>,
+    {
+        let mut new_content = Vec::new();
+        let (r, state) = callback(&mut new_content)?;
+        if state == IoState::Ok {
+            *flow_file.content.borrow_mut() = new_content;
+        }
+        Ok(r)
+    }
+
+    fn append<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
     where
         F: FnOnce(
             &mut dyn crate::api::process_session::OutputStream,
         ) -> Result<(R, IoState), MinifiError>,
     {
         let mut borrowed_content = flow_file.content.borrow_mut();
+        let original_len = borrowed_content.len();
         let mut flow_file_content = std::io::Cursor::new(&mut *borrowed_content);
-        let (r, _state) = callback(&mut flow_file_content)?;
-        Ok(r)
+        flow_file_content.set_position(original_len as u64);
+        let result = callback(&mut flow_file_content);
+        if !matches!(result, Ok((_, IoState::Ok))) {
+            borrowed_content.truncate(original_len);
+        }
+        result.map(|(r, _state)| r)
     }
 
     fn read(&self, flow_file: &Self::FlowFile) -> Option<Vec<u8>> {
         Some(flow_file.content.borrow().clone())
     }
 
-    fn read_stream<F, R>(&self, _flow_file: &Self::FlowFile, _callback: F) -> Result<R, MinifiError>
+    fn read_stream<F, R>(&self, flow_file: &Self::FlowFile, callback: F) -> Result<R, MinifiError>
     where
         F: FnOnce(&mut dyn InputStream) -> Result<R, MinifiError>,
     {
-        unimplemented!("Not implemented yet")
+        let mut input_stream = MockInputStream {
+            content: std::io::Cursor::new(flow_file.content.borrow().clone()),
+            seekable: self.seekable_content,
+        };
+        callback(&mut input_stream)
     }
 
     fn read_in_batches<F>(
@@ -125,17 +234,28 @@ impl MockProcessSession {
         Self {
             transferred_flow_files: RefCell::new(Vec::new()),
             input_flow_files: Vec::new(),
+            seekable_content: true,
         }
     }
 
     pub fn num_of_transferred_flow_files(&self) -> usize {
         self.transferred_flow_files.borrow().len()
     }
+
+    /// Works with dynamic relationships too, as transfers are recorded by name
+    pub fn num_of_flow_files_transferred_to(&self, relationship: &str) -> usize {
+        self.transferred_flow_files
+            .borrow()
+            .iter()
+            .filter(|transferred| transferred.relationship == relationship)
+            .count()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::DynamicRelationship;
 
     #[test]
     fn test_read_in_batches() {
@@ -155,4 +275,157 @@ mod tests {
         assert_eq!(vec.len(), 13);
         assert_eq!(vec, b"Hello, World!");
     }
+
+    #[test]
+    fn test_create_child_and_clone() {
+        let mut session = MockProcessSession::new();
+        let mut parent = MockFlowFile::with_content(b"parent content");
+        parent
+            .attributes
+            .insert("filename".to_string(), "foo.txt".to_string());
+
+        let child = session.create_child(&parent).unwrap();
+        assert!(child.is_child_of(&parent));
+        assert_eq!(child.content_len(), 0);
+        assert_eq!(child.attributes.get("filename").unwrap(), "foo.txt");
+
+        let clone = session.clone_flow_file(&parent).unwrap();
+        assert!(clone.is_child_of(&parent));
+        assert!(clone.content_eq("parent content"));
+
+        assert_eq!(*parent.children_ids.borrow(), vec![child.id, clone.id]);
+    }
+
+    #[test]
+    fn test_transfer_to_dynamic_relationship() {
+        let session = MockProcessSession::new();
+        let relationship = DynamicRelationship::new(format!("{}-{}", "route", 1));
+        session
+            .transfer(MockFlowFile::new(), relationship.name())
+            .unwrap();
+        session.transfer(MockFlowFile::new(), "success").unwrap();
+
+        assert_eq!(session.num_of_flow_files_transferred_to("route-1"), 1);
+        assert_eq!(session.num_of_flow_files_transferred_to("success"), 1);
+        assert_eq!(session.num_of_flow_files_transferred_to("failure"), 0);
+    }
+
+    #[test]
+    fn test_bulk_attributes() {
+        let session = MockProcessSession::new();
+        let mut flow_file = MockFlowFile::new();
+        session
+            .set_attributes(
+                &mut flow_file,
+                [
+                    ("http.status", "200"),
+                    ("http.method", "GET"),
+                    ("filename", "foo.txt"),
+                ],
+            )
+            .unwrap();
+        assert_eq!(session.get_attributes(&flow_file).len(), 3);
+
+        session
+            .remove_attribute(&mut flow_file, "filename")
+            .unwrap();
+        assert_eq!(session.get_attribute(&flow_file, "filename"), None);
+
+        session
+            .set_attribute(&mut flow_file, "x.http.status", "200")
+            .unwrap();
+        let removed = session
+            .remove_attributes_matching(&mut flow_file, r"http\..*")
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(session.get_attributes(&flow_file).len(), 1);
+
+        let removed = session
+            .remove_attributes_where(&mut flow_file, |key| key.starts_with("x."))
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert!(session.get_attributes(&flow_file).is_empty());
+
+        assert!(matches!(
+            session.remove_attributes_matching(&mut flow_file, "http.("),
+            Err(MinifiError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_stream_size_and_seek() {
+        let mut session = MockProcessSession::new();
+        let flow_file = MockFlowFile::with_content(b"header|body|trailer");
+
+        for seekable_content in [true, false] {
+            session.seekable_content = seekable_content;
+            let (size, trailer) = session
+                .read_stream(&flow_file, |input_stream| {
+                    assert_eq!(input_stream.as_seekable().is_some(), seekable_content);
+                    let mut header = [0u8; 7];
+                    input_stream.read_exact(&mut header)?;
+
+                    let mut seekable = input_stream.seekable()?;
+                    seekable.seek(SeekFrom::End(-7))?;
+                    let mut trailer = String::new();
+                    seekable.read_to_string(&mut trailer)?;
+                    // the buffered fallback cannot go back before what was already read
+                    assert_eq!(seekable.seek(SeekFrom::Start(0)).is_ok(), seekable_content);
+                    assert_eq!(seekable.seek(SeekFrom::Start(7))?, 7);
+                    Ok((input_stream.size(), trailer))
+                })
+                .unwrap();
+            assert_eq!(size, Some(19));
+            assert_eq!(trailer, "trailer");
+        }
+    }
+
+    #[test]
+    fn test_write_vectored() {
+        let session = MockProcessSession::new();
+        let flow_file = MockFlowFile::with_content(b"previous content");
+        for chunk_size in [0, 1, 4, 1024] {
+            session
+                .write_vectored(&flow_file, &[b"header|", b"", b"body"], chunk_size)
+                .unwrap();
+            assert!(flow_file.content_eq("header|body"));
+        }
+    }
+
+    #[test]
+    fn test_append_and_modify() {
+        let session = MockProcessSession::new();
+        let flow_file = MockFlowFile::with_content(b"body");
+
+        session
+            .modify(&flow_file, |input_stream, output_stream| {
+                output_stream.write_all(b"header|")?;
+                std::io::copy(input_stream, output_stream)?;
+                Ok(((), IoState::Ok))
+            })
+            .unwrap();
+        assert!(flow_file.content_eq("header|body"));
+
+        session
+            .append(&flow_file, |output_stream| {
+                output_stream.write_all(b"|footer")?;
+                Ok(((), IoState::Ok))
+            })
+            .unwrap();
+        assert!(flow_file.content_eq("header|body|footer"));
+
+        session
+            .append(&flow_file, |output_stream| {
+                output_stream.write_all(b"|cancelled")?;
+                Ok(((), IoState::Cancel))
+            })
+            .unwrap();
+        session
+            .modify(&flow_file, |_input_stream, output_stream| {
+                output_stream.write_all(b"cancelled")?;
+                Ok(((), IoState::Cancel))
+            })
+            .unwrap();
+        assert!(flow_file.content_eq("header|body|footer"));
+    }
 }
@@ -0,0 +1,63 @@
+use crate::api::RawProcessor;
+use crate::{
+    InstanceMetadata, MockFlowFile, MockLogger, MockProcessContext, MockProcessSession,
+    Relationship,
+};
+
+pub(crate) const SUCCESS: Relationship = Relationship {
+    name: "success",
+    description: "",
+};
+
+pub(crate) const FAILURE: Relationship = Relationship {
+    name: "failure",
+    description: "",
+};
+
+/// Declares a stateless processor for the wrapper tests, only the wrapper trait is left to implement
+macro_rules! test_processor {
+    ($name:ident) => {
+        struct $name;
+
+        impl $crate::Schedule for $name {
+            fn schedule<Ctx: $crate::GetProperty, L: $crate::Logger>(
+                _context: &Ctx,
+                _logger: &L,
+            ) -> Result<Self, $crate::MinifiError> {
+                Ok(Self)
+            }
+        }
+
+        impl $crate::CalculateMetrics for $name {}
+
+        impl $crate::AdvancedProcessorFeatures for $name {
+            fn get_trigger_when_empty(&self) -> bool {
+                false
+            }
+            fn is_work_available(&self) -> bool {
+                false
+            }
+        }
+    };
+}
+pub(crate) use test_processor;
+
+pub(crate) fn scheduled<P: RawProcessor<LoggerType = MockLogger>>() -> (P, MockProcessContext) {
+    let mut processor = P::new(InstanceMetadata::default(), MockLogger::new());
+    let context = MockProcessContext::new();
+    processor.on_schedule(&context).unwrap();
+    (processor, context)
+}
+
+/// A session with one incoming flow file
+pub(crate) fn session_with(content: &[u8], attributes: &[(&str, &str)]) -> MockProcessSession {
+    let mut flow_file = MockFlowFile::with_content(content);
+    for (key, value) in attributes {
+        flow_file
+            .attributes
+            .insert(key.to_string(), value.to_string());
+    }
+    let mut session = MockProcessSession::new();
+    session.input_flow_files.push(flow_file);
+    session
+}
@@ -2,6 +2,10 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{DeriveInput, parse_macro_input};
 
+mod metrics;
+mod processor_definition;
+mod properties;
+
 #[proc_macro_derive(ComponentIdentifier)]
 pub fn derive_component_identifier(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -40,7 +44,6 @@ pub fn derive_no_advanced_processor_features(input: TokenStream) -> TokenStream
     let name = &input.ident;
     let expanded = quote! {
         impl ::minifi_native::AdvancedProcessorFeatures for #name {
-                fn restore(&self) -> bool { false }
                 fn get_trigger_when_empty(&self) -> bool { false }
                 fn is_work_available(&self) -> bool { false }
         }
@@ -48,3 +51,72 @@ pub fn derive_no_advanced_processor_features(input: TokenStream) -> TokenStream
 
     TokenStream::from(expanded)
 }
+
+/// Implements ProcessorDefinition from a #[processor(...)] attribute
+///
+/// ```ignore
+/// #[derive(ProcessorDefinition)]
+/// #[processor(
+///     description = "Writes the contents of a FlowFile to the local file system.",
+///     input = Required, // Required (default), Allowed or Forbidden
+///     relationships = [SUCCESS, FAILURE],
+///     properties = [DIRECTORY, CONFLICT_RESOLUTION],
+///     output_attributes = [FILENAME],
+///     dynamic_properties = [ATTRIBUTE_EXPRESSION],
+///     supports_dynamic_properties, // optional flags, default to false
+///     supports_dynamic_relationships,
+/// )]
+/// struct PutFile {}
+/// ```
+/// Every relationship referenced by the output attributes must be listed in relationships,
+/// this is checked at compile time.
+#[proc_macro_derive(ProcessorDefinition, attributes(processor))]
+pub fn derive_processor_definition(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    processor_definition::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements Properties for a config struct, every field becomes an associated Property const
+/// (named after the field in upper case) and from_context loads and parses all of them
+///
+/// ```ignore
+/// #[derive(Properties)]
+/// struct GenerateFlowFileProperties {
+///     #[property(name = "File Size", description = "...", default = "1 kB", expression_language)]
+///     file_size: DataSize,
+///     #[property(name = "Custom Text", description = "...")]
+///     custom_text: Option<String>, // Option fields are not required
+/// }
+/// ```
+/// The validator and allowed values come from the PropertyValue impl of the field type,
+/// other keys are `sensitive` and `allowed_type = "..."`.
+#[proc_macro_derive(Properties, attributes(property))]
+pub fn derive_properties(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    properties::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements Metrics for a struct of Counter, Gauge and Histogram fields,
+/// every field is published under its name in PascalCase
+///
+/// ```ignore
+/// #[derive(Debug, Default, Metrics)]
+/// struct GetFileMetrics {
+///     accepted_files: Counter, // AcceptedFiles
+///     #[metric(name = "InputBytes")]
+///     bytes: Counter,
+///     #[metric(skip)]
+///     last_listing: Mutex<Option<Instant>>,
+/// }
+/// ```
+#[proc_macro_derive(Metrics, attributes(metric))]
+pub fn derive_metrics(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    metrics::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
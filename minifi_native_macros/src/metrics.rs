@@ -0,0 +1,96 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, Field, Fields};
+
+struct MetricAttributes {
+    name: Option<Expr>,
+    skip: bool,
+}
+
+impl MetricAttributes {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut attributes = MetricAttributes {
+            name: None,
+            skip: false,
+        };
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("metric")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    attributes.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("skip") {
+                    attributes.skip = true;
+                } else {
+                    return Err(meta.error("unsupported metric attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// accepted_files -> AcceptedFiles
+fn to_pascal_case(field_name: &str) -> String {
+    field_name
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "#[derive(Metrics)] only supports structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "#[derive(Metrics)] only supports structs",
+            ));
+        }
+    };
+
+    let mut publishers = Vec::new();
+    for field in fields {
+        let attributes = MetricAttributes::parse(field)?;
+        if attributes.skip {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().expect("named field");
+        let metric_name = match attributes.name {
+            Some(metric_name) => quote! { #metric_name },
+            None => {
+                let metric_name = to_pascal_case(&field_ident.to_string());
+                quote! { #metric_name }
+            }
+        };
+        publishers.push(quote! {
+            ::minifi_native::Metric::publish(&self.#field_ident, #metric_name, metrics);
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::minifi_native::Metrics for #name #ty_generics #where_clause {
+            fn collect_into(&self, metrics: &mut Vec<(String, f64)>) {
+                #(#publishers)*
+            }
+        }
+    })
+}
@@ -0,0 +1,145 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Expr, Ident, LitBool, LitStr};
+
+struct ProcessorAttributes {
+    description: Option<Expr>,
+    input_requirement: Ident,
+    supports_dynamic_properties: bool,
+    supports_dynamic_relationships: bool,
+    relationships: Option<Expr>,
+    properties: Option<Expr>,
+    output_attributes: Option<Expr>,
+    dynamic_properties: Option<Expr>,
+}
+
+impl ProcessorAttributes {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attributes = ProcessorAttributes {
+            description: None,
+            input_requirement: Ident::new("Required", proc_macro2::Span::call_site()),
+            supports_dynamic_properties: false,
+            supports_dynamic_relationships: false,
+            relationships: None,
+            properties: None,
+            output_attributes: None,
+            dynamic_properties: None,
+        };
+
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("processor"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("description") {
+                    attributes.description = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("input") {
+                    let requirement: Ident = meta.value()?.parse()?;
+                    match requirement.to_string().as_str() {
+                        "Required" | "Allowed" | "Forbidden" => {}
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                requirement,
+                                "input must be one of Required, Allowed or Forbidden",
+                            ));
+                        }
+                    }
+                    attributes.input_requirement = requirement;
+                } else if meta.path.is_ident("supports_dynamic_properties") {
+                    attributes.supports_dynamic_properties = parse_flag(&meta)?;
+                } else if meta.path.is_ident("supports_dynamic_relationships") {
+                    attributes.supports_dynamic_relationships = parse_flag(&meta)?;
+                } else if meta.path.is_ident("relationships") {
+                    attributes.relationships = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("properties") {
+                    attributes.properties = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("output_attributes") {
+                    attributes.output_attributes = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("dynamic_properties") {
+                    attributes.dynamic_properties = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported processor attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if attributes.description.is_none() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[derive(ProcessorDefinition)] requires #[processor(description = \"...\")]",
+            ));
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Flags can be written as `flag` or `flag = true`
+fn parse_flag(meta: &syn::meta::ParseNestedMeta) -> syn::Result<bool> {
+    if meta.input.peek(syn::Token![=]) {
+        Ok(meta.value()?.parse::<LitBool>()?.value)
+    } else {
+        Ok(true)
+    }
+}
+
+/// `[A, B]` becomes `&[A, B]`, anything else (e.g. a const fn call) is used as is
+fn as_static_slice(expr: Option<Expr>) -> TokenStream {
+    match expr {
+        None => quote! { &[] },
+        Some(Expr::Array(array)) => quote! { &#array },
+        Some(other) => quote! { #other },
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let attributes = ProcessorAttributes::parse(&input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let description = attributes.description;
+    let input_requirement = attributes.input_requirement;
+    let supports_dynamic_properties = attributes.supports_dynamic_properties;
+    let supports_dynamic_relationships = attributes.supports_dynamic_relationships;
+    let relationships = as_static_slice(attributes.relationships);
+    let properties = as_static_slice(attributes.properties);
+    let output_attributes = as_static_slice(attributes.output_attributes);
+    let dynamic_properties = as_static_slice(attributes.dynamic_properties);
+
+    // checked on the listed values rather than on the impl, so generic processors are checked too
+    // (a free const cannot name the impl of a generic type)
+    let message = LitStr::new(
+        &format!(
+            "an output attribute of {} refers to a relationship that is not listed in its relationships",
+            name
+        ),
+        name.span(),
+    );
+    let relationship_check = quote! {
+        const _: () = assert!(
+            ::minifi_native::output_attributes_match_relationships(
+                #output_attributes,
+                #relationships,
+            ),
+            #message
+        );
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::minifi_native::ProcessorDefinition for #name #ty_generics #where_clause {
+            const DESCRIPTION: &'static str = #description;
+            const INPUT_REQUIREMENT: ::minifi_native::ProcessorInputRequirement =
+                ::minifi_native::ProcessorInputRequirement::#input_requirement;
+            const SUPPORTS_DYNAMIC_PROPERTIES: bool = #supports_dynamic_properties;
+            const SUPPORTS_DYNAMIC_RELATIONSHIPS: bool = #supports_dynamic_relationships;
+            const OUTPUT_ATTRIBUTES: &'static [::minifi_native::OutputAttribute] = #output_attributes;
+            const RELATIONSHIPS: &'static [::minifi_native::Relationship] = #relationships;
+            const PROPERTIES: &'static [::minifi_native::Property] = #properties;
+            const DYNAMIC_PROPERTIES: &'static [::minifi_native::DynamicProperty] = #dynamic_properties;
+        }
+
+        #relationship_check
+    })
+}
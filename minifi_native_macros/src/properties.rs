@@ -0,0 +1,172 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Expr, Field, Fields, GenericArgument, PathArguments, Type};
+
+struct PropertyAttributes {
+    name: Option<Expr>,
+    description: Option<Expr>,
+    default_value: Option<Expr>,
+    is_sensitive: bool,
+    supports_expr_lang: bool,
+    allowed_type: Option<Expr>,
+}
+
+impl PropertyAttributes {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut attributes = PropertyAttributes {
+            name: None,
+            description: None,
+            default_value: None,
+            is_sensitive: false,
+            supports_expr_lang: false,
+            allowed_type: None,
+        };
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("property")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    attributes.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("description") {
+                    attributes.description = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default") {
+                    attributes.default_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("sensitive") {
+                    attributes.is_sensitive = true;
+                } else if meta.path.is_ident("expression_language") {
+                    attributes.supports_expr_lang = true;
+                } else if meta.path.is_ident("allowed_type") {
+                    attributes.allowed_type = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unsupported property attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if attributes.name.is_none() || attributes.description.is_none() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "#[derive(Properties)] requires #[property(name = \"...\", description = \"...\")] on every field",
+            ));
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Returns T for Option<T>
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "#[derive(Properties)] only supports structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "#[derive(Properties)] only supports structs",
+            ));
+        }
+    };
+
+    let mut property_consts = Vec::new();
+    let mut const_idents = Vec::new();
+    let mut field_loaders = Vec::new();
+
+    for field in fields {
+        let attributes = PropertyAttributes::parse(field)?;
+        let field_ident = field.ident.as_ref().expect("named field");
+        let const_ident = format_ident!("{}", field_ident.to_string().to_uppercase());
+
+        let (value_type, is_required) = match option_inner_type(&field.ty) {
+            Some(inner) => (inner, false),
+            None => (&field.ty, true),
+        };
+
+        let property_name = attributes.name;
+        let description = attributes.description;
+        let is_sensitive = attributes.is_sensitive;
+        let supports_expr_lang = attributes.supports_expr_lang;
+        let default_value = match attributes.default_value {
+            Some(default_value) => quote! { Some(#default_value) },
+            None => quote! { None },
+        };
+        let allowed_type = match attributes.allowed_type {
+            Some(allowed_type) => quote! { #allowed_type },
+            None => quote! { "" },
+        };
+
+        property_consts.push(quote! {
+            #vis const #const_ident: ::minifi_native::Property = ::minifi_native::Property {
+                name: #property_name,
+                description: #description,
+                is_required: #is_required,
+                is_sensitive: #is_sensitive,
+                supports_expr_lang: #supports_expr_lang,
+                default_value: #default_value,
+                validator: <#value_type as ::minifi_native::PropertyValue>::VALIDATOR,
+                allowed_values: <#value_type as ::minifi_native::PropertyValue>::ALLOWED_VALUES,
+                allowed_type: #allowed_type,
+            };
+        });
+
+        let get_value = quote! {
+            ::minifi_native::GetProperty::get_typed_property::<#value_type>(context, &Self::#const_ident)?
+        };
+        field_loaders.push(if is_required {
+            quote! {
+                #field_ident: #get_value.ok_or(
+                    ::minifi_native::MinifiError::MissingRequiredProperty(Self::#const_ident.name),
+                )?
+            }
+        } else {
+            quote! { #field_ident: #get_value }
+        });
+
+        const_idents.push(const_ident);
+    }
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#property_consts)*
+        }
+
+        impl #impl_generics ::minifi_native::Properties for #name #ty_generics #where_clause {
+            const PROPERTIES: &'static [::minifi_native::Property] = &[#(Self::#const_idents),*];
+
+            fn from_context<Ctx: ::minifi_native::GetProperty>(
+                context: &Ctx,
+            ) -> Result<Self, ::minifi_native::MinifiError> {
+                Ok(Self {
+                    #(#field_loaders),*
+                })
+            }
+        }
+    })
+}